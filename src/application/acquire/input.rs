@@ -1,30 +1,46 @@
 use gtk::prelude::*;
+use relm4::ComponentController as _;
 
 use crate::color::Colorable;
 
 #[derive(Debug)]
 pub enum InputMsg {
     Attenuation(u8),
+    ClearPersistence,
     Gain(redpitaya_scpi::acquire::Gain),
+    Persistence(crate::persistence::Persistence),
+    Rate(redpitaya_scpi::acquire::SamplingRate),
+    Reapply,
+    Restore(crate::application::session::Input),
+    Scale(f64),
     SetData(Vec<f64>),
-    Redraw(Box<gtk::cairo::Context>, Box<crate::application::Data>),
     Start,
     Stop,
 }
 
 #[derive(Debug)]
 pub enum OutputMsg {
+    Measure(crate::measure::Measurements),
+    Rescale,
     Start,
     Stop,
 }
 
 pub struct Model {
-    acquire: redpitaya_scpi::acquire::Acquire,
+    acquire: crate::redpitaya::Acquire,
     attenuation_radio: relm4::Controller<crate::widget::RadioGroup<u8>>,
     attenuation: u8,
     data: Vec<f64>,
-    gain: relm4::Controller<crate::widget::RadioGroup<redpitaya_scpi::acquire::Gain>>,
+    full_scale: f64,
+    gain: redpitaya_scpi::acquire::Gain,
+    gain_radio: relm4::Controller<crate::widget::RadioGroup<redpitaya_scpi::acquire::Gain>>,
+    name: String,
     palette: relm4::Controller<crate::widget::Palette>,
+    persistence: crate::persistence::Persistence,
+    persistence_grid: crate::persistence::Grid,
+    sample_period: f64,
+    scale: f64,
+    scale_select: relm4::Controller<crate::widget::Division>,
     source: redpitaya_scpi::acquire::Source,
     started: bool,
 }
@@ -32,8 +48,9 @@ pub struct Model {
 #[relm4::component(pub)]
 impl relm4::SimpleComponent for Model {
     type Init = (
-        redpitaya_scpi::acquire::Acquire,
+        crate::redpitaya::Acquire,
         redpitaya_scpi::acquire::Source,
+        String,
     );
     type Input = InputMsg;
     type Output = OutputMsg;
@@ -44,23 +61,27 @@ impl relm4::SimpleComponent for Model {
         sender: relm4::ComponentSender<Self>,
     ) -> relm4::ComponentParts<Self> {
         use relm4::Component as _;
-        use relm4::ComponentController as _;
         use relm4::RelmContainerExt as _;
 
         let palette = crate::widget::Palette::builder()
-            .launch((init.1.to_string(), init.1.into()))
+            .launch((init.2.clone(), init.2.clone().into()))
             .forward(sender.input_sender(), |output| match output {
                 crate::widget::palette::OutputMsg::Expand => InputMsg::Start,
                 crate::widget::palette::OutputMsg::Fold => InputMsg::Stop,
             });
 
-        let gain = crate::widget::RadioGroup::builder()
+        let gain = init
+            .0
+            .gain(init.1)
+            .unwrap_or(redpitaya_scpi::acquire::Gain::LV);
+
+        let gain_radio = crate::widget::RadioGroup::builder()
             .launch(crate::widget::radio::Options {
                 options: vec![
                     redpitaya_scpi::acquire::Gain::LV,
                     redpitaya_scpi::acquire::Gain::HV,
                 ],
-                current: init.0.gain(init.1).ok(),
+                current: Some(gain),
                 label: "Gain",
             })
             .forward(sender.input_sender(), |output| {
@@ -79,15 +100,41 @@ impl relm4::SimpleComponent for Model {
                 InputMsg::Attenuation(attenuation)
             });
 
+        let scale_select = crate::widget::Division::builder()
+            .launch(crate::widget::division::Options {
+                label: "Gain",
+                range: crate::scales::V_DIV,
+                current: 1.0,
+            })
+            .forward(sender.input_sender(), |output| {
+                let crate::widget::division::OutputMsg::Change(scale) = output;
+                InputMsg::Scale(scale)
+            });
+
+        let sample_period = crate::measure::sample_period(
+            init.0
+                .decimation()
+                .map(Into::into)
+                .unwrap_or(redpitaya_scpi::acquire::SamplingRate::RATE_125MHz),
+        );
+
         let model = Self {
             acquire: init.0,
             attenuation: 1,
             attenuation_radio,
             data: Vec::new(),
+            full_scale: Self::full_scale(gain),
             gain,
+            gain_radio,
+            name: init.2,
             started: false,
             source: init.1,
             palette,
+            persistence: crate::persistence::Persistence::default(),
+            persistence_grid: crate::persistence::Grid::new(1_024, 512),
+            sample_period,
+            scale: 1.0,
+            scale_select,
         };
 
         let widgets = view_output!();
@@ -101,10 +148,63 @@ impl relm4::SimpleComponent for Model {
         use InputMsg::*;
 
         match msg {
-            Attenuation(attenuation) => self.attenuation = attenuation,
-            Gain(gain) => self.acquire.set_gain(self.source, gain),
-            Redraw(context, model) => self.draw(&context, &model).unwrap(),
-            SetData(data) => self.data = data,
+            Attenuation(attenuation) => {
+                self.attenuation = attenuation;
+                self.persistence_grid.clear();
+                sender.output(OutputMsg::Rescale).ok();
+            }
+            ClearPersistence => self.persistence_grid.clear(),
+            Gain(gain) => {
+                self.gain = gain;
+                self.acquire.set_gain(self.source, gain).ok();
+                self.full_scale = Self::full_scale(gain);
+                self.persistence_grid.clear();
+            }
+            Persistence(persistence) => {
+                self.persistence = persistence;
+                self.persistence_grid.clear();
+            }
+            Rate(rate) => {
+                self.sample_period = crate::measure::sample_period(rate);
+                self.persistence_grid.clear();
+            }
+            Reapply => {
+                self.acquire.set_gain(self.source, self.gain).ok();
+            }
+            Restore(input) => {
+                self.gain_radio
+                    .emit(crate::widget::radio::InputMsg::Set(input.gain));
+                self.attenuation_radio
+                    .emit(crate::widget::radio::InputMsg::Set(input.attenuation));
+                self.scale_select
+                    .emit(crate::widget::division::InputMsg::Set(input.scale));
+                self.palette
+                    .emit(crate::widget::palette::InputMsg::Set(input.enable));
+            }
+            Scale(scale) => {
+                self.scale = scale;
+                sender.output(OutputMsg::Rescale).ok();
+            }
+            SetData(data) => {
+                self.data = data;
+
+                if let Some((_, signal)) = self.signal() {
+                    if self.persistence != crate::persistence::Persistence::Off {
+                        self.persistence_grid.add(
+                            &signal,
+                            self.full_scale * self.attenuation as f64,
+                            self.persistence,
+                        );
+                    }
+
+                    sender
+                        .output(OutputMsg::Measure(crate::measure::measure(
+                            &signal,
+                            self.sample_period,
+                        )))
+                        .ok();
+                }
+            }
             Start => {
                 self.started = true;
                 sender.output(OutputMsg::Start).ok();
@@ -130,8 +230,9 @@ impl relm4::SimpleComponent for Model {
             set_orientation: gtk::Orientation::Vertical,
             set_spacing: 10,
 
-            append: model.gain.widget(),
+            append: model.gain_radio.widget(),
             append: model.attenuation_radio.widget(),
+            append: model.scale_select.widget(),
         },
     }
 }
@@ -141,7 +242,31 @@ impl Model {
         self.started
     }
 
-    fn draw(
+    pub(super) fn session(&self) -> crate::application::session::Input {
+        crate::application::session::Input {
+            enable: self.started,
+            gain: self.gain,
+            attenuation: self.attenuation,
+            scale: self.scale,
+        }
+    }
+
+    pub(super) fn trace(&self, data: &crate::application::Data) -> Option<(String, Vec<f64>)> {
+        if !self.is_started() || self.data.is_empty() {
+            return None;
+        }
+
+        let offset = data.offset(&self.name) * self.scale;
+        let trace = self
+            .data
+            .iter()
+            .map(|sample| sample * self.attenuation as f64 + offset)
+            .collect();
+
+        Some((format!("{} (V)", self.name), trace))
+    }
+
+    pub(super) fn draw(
         &self,
         context: &gtk::cairo::Context,
         data: &crate::application::Data,
@@ -150,22 +275,149 @@ impl Model {
             return Ok(());
         }
 
-        context.set_color(self.source.into());
+        context.set_color(self.name.clone().into());
 
-        context.translate(0.0, data.offset(self.source));
+        context.translate(0.0, data.offset(&self.name));
 
         context.move_to(data.scales.h.0, 0.0);
         context.line_to(data.scales.h.1, 0.0);
         context.stroke()?;
 
-        self.draw_data(context, data.scales, self.attenuation)
+        if self.persistence == crate::persistence::Persistence::Off {
+            self.draw_data(context, data.scales, self.attenuation as f64 / self.scale)
+        } else {
+            self.draw_persistence(context, data.scales)
+        }
+    }
+
+    pub(super) fn axis(&self, data: &crate::application::Data) -> Option<crate::application::Axis> {
+        if !self.is_started() {
+            return None;
+        }
+
+        Some(crate::application::Axis {
+            name: self.name.clone(),
+            color: self.name.clone().into(),
+            offset: data.offset(&self.name),
+            scale: self.scale,
+        })
+    }
+
+    pub(super) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(super) fn attenuation(&self) -> u8 {
+        self.attenuation
+    }
+
+    pub(super) fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    pub(super) fn signal(&self) -> Option<(String, Vec<f64>)> {
+        if !self.is_started() || self.data.is_empty() {
+            return None;
+        }
+
+        let signal = self
+            .data
+            .iter()
+            .map(|sample| sample * self.attenuation as f64)
+            .collect();
+
+        Some((self.name.clone(), signal))
+    }
+
+    pub(super) fn samples(
+        &self,
+        data: &crate::application::Data,
+        times: &[f64],
+    ) -> Option<(String, Vec<f64>)> {
+        if !self.is_started() || self.data.is_empty() {
+            return None;
+        }
+
+        let values = times
+            .iter()
+            .map(|time| {
+                let sample = data.scales.ms_to_sample(*time).round();
+
+                if sample < 0.0 {
+                    return f64::NAN;
+                }
+
+                self.data
+                    .get(sample as usize)
+                    .map_or(f64::NAN, |value| value * self.attenuation as f64)
+            })
+            .collect();
+
+        Some((self.name.clone(), values))
+    }
+
+    fn full_scale(gain: redpitaya_scpi::acquire::Gain) -> f64 {
+        match gain {
+            redpitaya_scpi::acquire::Gain::LV => 1.0,
+            redpitaya_scpi::acquire::Gain::HV => 20.0,
+        }
+    }
+
+    fn draw_persistence(
+        &self,
+        context: &gtk::cairo::Context,
+        scales: crate::Scales,
+    ) -> Result<(), gtk::cairo::Error> {
+        let grid = &self.persistence_grid;
+        let format = gtk::cairo::Format::ARgb32;
+        let stride = format.stride_for_width(grid.columns as u32)?;
+        let mut pixels = vec![0_u8; stride as usize * grid.rows];
+
+        for (cell, intensity) in grid.intensities().into_iter().enumerate() {
+            if intensity <= 0.0 {
+                continue;
+            }
+
+            let (r, g, b) = crate::persistence::heat(intensity);
+            let pixel = 0xff00_0000
+                | ((r * 255.0) as u32) << 16
+                | ((g * 255.0) as u32) << 8
+                | (b * 255.0) as u32;
+            let offset = cell / grid.columns * stride as usize + cell % grid.columns * 4;
+
+            pixels[offset..offset + 4].copy_from_slice(&pixel.to_ne_bytes());
+        }
+
+        let image = gtk::cairo::ImageSurface::create_for_data(
+            pixels,
+            format,
+            grid.columns as i32,
+            grid.rows as i32,
+            stride,
+        )?;
+
+        let range = self.full_scale * self.attenuation as f64 / self.scale;
+        let (x0, x1) = (
+            scales.sample_to_ms(0),
+            scales.sample_to_ms(scales.n_samples),
+        );
+
+        context.translate(x0, range);
+        context.scale(
+            (x1 - x0) / grid.columns as f64,
+            -2.0 * range / grid.rows as f64,
+        );
+        context.set_source_surface(&image, 0.0, 0.0)?;
+        context.source().set_filter(gtk::cairo::Filter::Nearest);
+        context.rectangle(0.0, 0.0, grid.columns as f64, grid.rows as f64);
+        context.fill()
     }
 
     fn draw_data(
         &self,
         context: &gtk::cairo::Context,
         scales: crate::Scales,
-        attenuation: u8,
+        gain: f64,
     ) -> Result<(), gtk::cairo::Error> {
         if self.data.is_empty() {
             return Ok(());
@@ -173,12 +425,11 @@ impl Model {
 
         context.set_line_width(0.05);
 
-        for sample in 0..scales.n_samples {
-            let x = scales.sample_to_ms(sample);
-            let y = self.data[sample as usize];
+        for (sample, y) in self.data.iter().take(scales.n_samples as usize).enumerate() {
+            let x = scales.sample_to_ms(sample as u32);
 
-            context.line_to(x, y * attenuation as f64);
-            context.move_to(x, y * attenuation as f64);
+            context.line_to(x, y * gain);
+            context.move_to(x, y * gain);
         }
         context.stroke()
     }
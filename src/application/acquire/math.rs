@@ -0,0 +1,494 @@
+use gtk::prelude::*;
+use relm4::ComponentController as _;
+
+use crate::color::Colorable;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Operation {
+    #[default]
+    Sum,
+    Difference,
+    Product,
+    Ratio,
+    Derivative,
+    Integral,
+    Expression,
+}
+
+impl std::fmt::Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let display = match self {
+            Self::Sum => "IN 1 + IN 2",
+            Self::Difference => "IN 1 - IN 2",
+            Self::Product => "IN 1 × IN 2",
+            Self::Ratio => "IN 1 ÷ IN 2",
+            Self::Derivative => "Derivative",
+            Self::Integral => "Integral",
+            Self::Expression => "Expression",
+        };
+
+        write!(f, "{display}")
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Operand {
+    #[default]
+    In1,
+    In2,
+}
+
+impl std::fmt::Display for Operand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let display = match self {
+            Self::In1 => "IN 1",
+            Self::In2 => "IN 2",
+        };
+
+        write!(f, "{display}")
+    }
+}
+
+#[derive(Debug)]
+pub enum InputMsg {
+    Expression(String),
+    Operand(Operand),
+    Operation(Operation),
+    Rate(redpitaya_scpi::acquire::SamplingRate),
+    Restore(crate::application::session::Math),
+    Scale(f64),
+    SetData(redpitaya_scpi::acquire::Source, Vec<f64>),
+    Start,
+    Stop,
+}
+
+#[derive(Debug)]
+pub enum OutputMsg {
+    Measure(crate::measure::Measurements),
+    Start,
+    Stop,
+}
+
+pub struct Model {
+    buffer: gtk::EntryBuffer,
+    data: Vec<f64>,
+    error: String,
+    expression: Option<crate::expression::Expression>,
+    fresh: [bool; 2],
+    inputs: [Vec<f64>; 2],
+    name: String,
+    operand: Operand,
+    operand_radio: relm4::Controller<crate::widget::RadioGroup<Operand>>,
+    operation: Operation,
+    operation_radio: relm4::Controller<crate::widget::RadioGroup<Operation>>,
+    palette: relm4::Controller<crate::widget::Palette>,
+    sample_period: f64,
+    scale: f64,
+    scale_select: relm4::Controller<crate::widget::Division>,
+    source: String,
+    started: bool,
+}
+
+#[relm4::component(pub)]
+impl relm4::SimpleComponent for Model {
+    type Init = (String, f64);
+    type Input = InputMsg;
+    type Output = OutputMsg;
+
+    fn init(
+        init: Self::Init,
+        _root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        use relm4::Component as _;
+        use relm4::RelmContainerExt as _;
+
+        let (name, sample_period) = init;
+
+        let palette = crate::widget::Palette::builder()
+            .launch((name.clone(), name.clone().into()))
+            .forward(sender.input_sender(), |output| match output {
+                crate::widget::palette::OutputMsg::Expand => InputMsg::Start,
+                crate::widget::palette::OutputMsg::Fold => InputMsg::Stop,
+            });
+
+        let operation_radio = crate::widget::RadioGroup::builder()
+            .launch(crate::widget::radio::Options {
+                options: vec![
+                    Operation::Sum,
+                    Operation::Difference,
+                    Operation::Product,
+                    Operation::Ratio,
+                    Operation::Derivative,
+                    Operation::Integral,
+                    Operation::Expression,
+                ],
+                current: Some(Operation::Sum),
+                label: "Operation",
+            })
+            .forward(sender.input_sender(), |output| {
+                let crate::widget::radio::OutputMsg::Change(operation) = output;
+                InputMsg::Operation(operation)
+            });
+
+        let operand_radio = crate::widget::RadioGroup::builder()
+            .launch(crate::widget::radio::Options {
+                options: vec![Operand::In1, Operand::In2],
+                current: Some(Operand::In1),
+                label: "Source",
+            })
+            .forward(sender.input_sender(), |output| {
+                let crate::widget::radio::OutputMsg::Change(operand) = output;
+                InputMsg::Operand(operand)
+            });
+        operand_radio.widget().set_visible(false);
+
+        let scale_select = crate::widget::Division::builder()
+            .launch(crate::widget::division::Options {
+                label: "Gain",
+                range: crate::scales::V_DIV,
+                current: 1.0,
+            })
+            .forward(sender.input_sender(), |output| {
+                let crate::widget::division::OutputMsg::Change(scale) = output;
+                InputMsg::Scale(scale)
+            });
+
+        let mut model = Self {
+            buffer: gtk::EntryBuffer::new(Some("in1 - in2")),
+            data: Vec::new(),
+            error: String::new(),
+            expression: None,
+            fresh: [false; 2],
+            inputs: [Vec::new(), Vec::new()],
+            name,
+            operand: Operand::In1,
+            operand_radio,
+            operation: Operation::Sum,
+            operation_radio,
+            palette,
+            sample_period,
+            scale: 1.0,
+            scale_select,
+            source: "in1 - in2".to_string(),
+            started: false,
+        };
+
+        model.parse();
+
+        let widgets = view_output!();
+
+        model.palette.widgets().container_add(&widgets.child);
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>) {
+        use InputMsg::*;
+
+        match msg {
+            Expression(source) => {
+                self.source = source;
+                self.parse();
+                self.compute(&sender);
+            }
+            Operand(operand) => {
+                self.operand = operand;
+                self.compute(&sender);
+            }
+            Operation(operation) => {
+                self.operation = operation;
+                self.operand_radio.widget().set_visible(matches!(
+                    operation,
+                    self::Operation::Derivative | self::Operation::Integral
+                ));
+                self.compute(&sender);
+            }
+            Rate(rate) => self.sample_period = crate::measure::sample_period(rate),
+            Restore(math) => {
+                self.buffer.set_text(&math.expression);
+                self.source = math.expression;
+                self.parse();
+                self.operation_radio
+                    .emit(crate::widget::radio::InputMsg::Set(math.operation));
+                self.operand_radio
+                    .emit(crate::widget::radio::InputMsg::Set(math.operand));
+                self.scale_select
+                    .emit(crate::widget::division::InputMsg::Set(math.scale));
+                self.palette
+                    .emit(crate::widget::palette::InputMsg::Set(math.enable));
+            }
+            Scale(scale) => self.scale = scale,
+            SetData(source, data) => {
+                let index = match source {
+                    redpitaya_scpi::acquire::Source::IN1 => 0,
+                    redpitaya_scpi::acquire::Source::IN2 => 1,
+                };
+
+                self.inputs[index] = data;
+                self.fresh[index] = true;
+
+                if self.fresh == [true; 2] {
+                    self.fresh = [false; 2];
+                    self.compute(&sender);
+                }
+            }
+            Start => {
+                self.started = true;
+                self.compute(&sender);
+                sender.output(OutputMsg::Start).ok();
+            }
+            Stop => {
+                self.started = false;
+                sender.output(OutputMsg::Stop).ok();
+            }
+        };
+    }
+
+    view! {
+        #[name = "page"]
+        #[root]
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: 10,
+
+            append: model.palette.widget(),
+        },
+        #[name = "child"]
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: 10,
+
+            append: model.operation_radio.widget(),
+            append: model.operand_radio.widget(),
+
+            gtk::Entry {
+                #[watch]
+                set_visible: model.operation == Operation::Expression,
+                set_placeholder_text: Some("in1, in2, t (s), sin, sqrt…"),
+                set_buffer: &model.buffer,
+
+                connect_activate[sender] => move |this| {
+                    sender.input(InputMsg::Expression(this.text().to_string()));
+                },
+            },
+            gtk::Label {
+                add_css_class: "error",
+                #[watch]
+                set_label: &model.error,
+                #[watch]
+                set_visible: model.operation == Operation::Expression && !model.error.is_empty(),
+            },
+
+            append: model.scale_select.widget(),
+        },
+    }
+}
+
+impl Model {
+    fn is_started(&self) -> bool {
+        self.started
+    }
+
+    pub(super) fn session(&self) -> crate::application::session::Math {
+        crate::application::session::Math {
+            enable: self.started,
+            operation: self.operation,
+            operand: self.operand,
+            expression: self.source.clone(),
+            scale: self.scale,
+        }
+    }
+
+    fn parse(&mut self) {
+        match self.source.parse::<crate::expression::Expression>() {
+            Ok(expression) => {
+                let unknown = expression
+                    .variables()
+                    .into_iter()
+                    .filter(|variable| !["in1", "in2", "t"].contains(&variable.as_str()))
+                    .collect::<Vec<_>>();
+
+                if unknown.is_empty() {
+                    self.error.clear();
+                    self.expression = Some(expression);
+                } else {
+                    self.error = format!("Unknown variable {}", unknown.join(", "));
+                    self.expression = None;
+                }
+            }
+            Err(err) => {
+                self.error = err;
+                self.expression = None;
+            }
+        }
+    }
+
+    fn compute(&mut self, sender: &relm4::ComponentSender<Self>) {
+        let [in1, in2] = &self.inputs;
+        let operand = match self.operand {
+            Operand::In1 => in1,
+            Operand::In2 => in2,
+        };
+        let dt = self.sample_period;
+        let binary = |f: fn(f64, f64) -> f64| -> Vec<f64> {
+            in1.iter().zip(in2).map(|(a, b)| f(*a, *b)).collect()
+        };
+
+        self.data = match self.operation {
+            Operation::Sum => binary(|a, b| a + b),
+            Operation::Difference => binary(|a, b| a - b),
+            Operation::Product => binary(|a, b| a * b),
+            Operation::Ratio => binary(|a, b| a / b),
+            Operation::Derivative => (0..operand.len())
+                .map(|i| {
+                    let (previous, next) = (i.saturating_sub(1), (i + 1).min(operand.len() - 1));
+
+                    (operand[next] - operand[previous]) / ((next - previous).max(1) as f64 * dt)
+                })
+                .collect(),
+            Operation::Integral => operand
+                .iter()
+                .scan((0.0, None), |(sum, previous), sample| {
+                    if let Some(previous) = *previous {
+                        *sum += (previous + sample) / 2.0 * dt;
+                    }
+                    *previous = Some(*sample);
+
+                    Some(*sum)
+                })
+                .collect(),
+            Operation::Expression => match &self.expression {
+                Some(expression) => in1
+                    .iter()
+                    .zip(in2)
+                    .enumerate()
+                    .map(|(i, (in1, in2))| {
+                        expression.eval(&|variable| match variable {
+                            "in1" => Some(*in1),
+                            "in2" => Some(*in2),
+                            "t" => Some(i as f64 * dt),
+                            _ => None,
+                        })
+                    })
+                    .collect(),
+                None => Vec::new(),
+            },
+        };
+
+        if self.is_started() && !self.data.is_empty() {
+            sender
+                .output(OutputMsg::Measure(crate::measure::measure(
+                    &self.finite(),
+                    self.sample_period,
+                )))
+                .ok();
+        }
+    }
+
+    fn finite(&self) -> Vec<f64> {
+        self.data
+            .iter()
+            .copied()
+            .filter(|sample| sample.is_finite())
+            .collect()
+    }
+
+    pub(super) fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    pub(super) fn signal(&self) -> Option<(String, Vec<f64>)> {
+        if !self.is_started() || self.data.is_empty() {
+            return None;
+        }
+
+        Some((self.name.clone(), self.data.clone()))
+    }
+
+    pub(super) fn trace(&self, data: &crate::application::Data) -> Option<(String, Vec<f64>)> {
+        let (_, signal) = self.signal()?;
+        let offset = data.offset(&self.name) * self.scale;
+
+        Some((
+            format!("{} (V)", self.name),
+            signal.iter().map(|sample| sample + offset).collect(),
+        ))
+    }
+
+    pub(super) fn samples(
+        &self,
+        data: &crate::application::Data,
+        times: &[f64],
+    ) -> Option<(String, Vec<f64>)> {
+        if !self.is_started() || self.data.is_empty() {
+            return None;
+        }
+
+        let values = times
+            .iter()
+            .map(|time| {
+                let sample = data.scales.ms_to_sample(*time).round();
+
+                if sample < 0.0 {
+                    return f64::NAN;
+                }
+
+                self.data.get(sample as usize).copied().unwrap_or(f64::NAN)
+            })
+            .collect();
+
+        Some((self.name.clone(), values))
+    }
+
+    pub(super) fn axis(&self, data: &crate::application::Data) -> Option<crate::application::Axis> {
+        if !self.is_started() {
+            return None;
+        }
+
+        Some(crate::application::Axis {
+            name: self.name.clone(),
+            color: self.name.clone().into(),
+            offset: data.offset(&self.name),
+            scale: self.scale,
+        })
+    }
+
+    pub(super) fn draw(
+        &self,
+        context: &gtk::cairo::Context,
+        data: &crate::application::Data,
+    ) -> Result<(), gtk::cairo::Error> {
+        if !self.is_started() {
+            return Ok(());
+        }
+
+        context.set_color(self.name.clone().into());
+
+        context.translate(0.0, data.offset(&self.name));
+
+        context.move_to(data.scales.h.0, 0.0);
+        context.line_to(data.scales.h.1, 0.0);
+        context.stroke()?;
+
+        context.set_line_width(0.05);
+
+        for (sample, value) in self
+            .data
+            .iter()
+            .enumerate()
+            .take(data.scales.n_samples as usize)
+        {
+            let x = data.scales.sample_to_ms(sample as u32);
+            let y = value / self.scale;
+
+            if y.is_finite() {
+                context.line_to(x, y);
+            } else {
+                context.new_path();
+            }
+        }
+
+        context.stroke()
+    }
+}
@@ -1,43 +1,89 @@
 mod input;
+pub(super) mod math;
 
 use gtk::prelude::*;
 use relm4::ComponentController as _;
 
 pub struct Model {
+    average: bool,
+    decimation: redpitaya_scpi::acquire::Decimation,
+    export_dialog: relm4::Controller<relm4_components::save_dialog::SaveDialog>,
     in1: relm4::Controller<input::Model>,
     in2: relm4::Controller<input::Model>,
+    math1: relm4::Controller<math::Model>,
+    math2: relm4::Controller<math::Model>,
     rate: relm4::Controller<crate::widget::RadioGroup<redpitaya_scpi::acquire::SamplingRate>>,
-    rp: redpitaya_scpi::acquire::Acquire,
+    rp: crate::redpitaya::Acquire,
 }
 
 #[derive(Debug)]
 pub enum InputMsg {
     Average(bool),
+    ClearPersistence,
+    Export,
+    Persistence(crate::persistence::Persistence),
+    Reapply,
+    Restore(crate::application::session::Acquire),
     SetData(redpitaya_scpi::acquire::Source, Vec<f64>),
     Rate(redpitaya_scpi::acquire::SamplingRate),
-    Redraw(Box<gtk::cairo::Context>, Box<crate::application::Data>),
+    SetRate(redpitaya_scpi::acquire::SamplingRate),
 }
 
 #[derive(Debug)]
 pub enum OutputMsg {
+    Export(std::path::PathBuf),
+    Measure(String, crate::measure::Measurements),
     Rate(redpitaya_scpi::acquire::SamplingRate),
-    Start(redpitaya_scpi::acquire::Source),
-    Stop(redpitaya_scpi::acquire::Source),
+    Rescale,
+    Start(String),
+    Stop(String),
 }
 
 #[relm4::component(pub)]
 impl relm4::SimpleComponent for Model {
-    type Init = redpitaya_scpi::acquire::Acquire;
+    type Init = (crate::redpitaya::Acquire, usize);
     type Input = InputMsg;
     type Output = OutputMsg;
 
     fn init(
         init: Self::Init,
-        _root: Self::Root,
+        root: Self::Root,
         sender: relm4::ComponentSender<Self>,
     ) -> relm4::ComponentParts<Self> {
         use relm4::Component as _;
 
+        let (init, board) = init;
+
+        let csv = gtk::FileFilter::new();
+        csv.set_name(Some("CSV"));
+        csv.add_pattern("*.csv");
+
+        let npy = gtk::FileFilter::new();
+        npy.set_name(Some("NumPy"));
+        npy.add_pattern("*.npy");
+
+        let export_dialog = relm4_components::save_dialog::SaveDialog::builder()
+            .transient_for_native(&root)
+            .launch(relm4_components::save_dialog::SaveDialogSettings {
+                filters: vec![csv, npy],
+                ..Default::default()
+            })
+            .connect_receiver(gtk::glib::clone!(
+                #[strong]
+                sender,
+                move |_, response| {
+                    if let relm4_components::save_dialog::SaveDialogResponse::Accept(path) =
+                        response
+                    {
+                        sender.output(OutputMsg::Export(path)).ok();
+                    }
+                }
+            ));
+
+        let decimation = init
+            .decimation()
+            .unwrap_or(redpitaya_scpi::acquire::Decimation::DEC_1);
+
         let rate = crate::widget::RadioGroup::builder()
             .launch(crate::widget::radio::Options {
                 options: vec![
@@ -48,7 +94,7 @@ impl relm4::SimpleComponent for Model {
                     redpitaya_scpi::acquire::SamplingRate::RATE_15_6MHz,
                     redpitaya_scpi::acquire::SamplingRate::RATE_125MHz,
                 ],
-                current: init.decimation().map(Into::into).ok(),
+                current: Some(decimation.into()),
                 label: "Samping Rate",
             })
             .forward(sender.input_sender(), |output| {
@@ -57,23 +103,66 @@ impl relm4::SimpleComponent for Model {
             });
 
         let in1 = input::Model::builder()
-            .launch((init.clone(), redpitaya_scpi::acquire::Source::IN1))
-            .forward(sender.output_sender(), |output| match output {
-                input::OutputMsg::Start => OutputMsg::Start(redpitaya_scpi::acquire::Source::IN1),
-                input::OutputMsg::Stop => OutputMsg::Stop(redpitaya_scpi::acquire::Source::IN1),
+            .launch((
+                init.clone(),
+                redpitaya_scpi::acquire::Source::IN1,
+                name(board, "IN 1"),
+            ))
+            .forward(sender.output_sender(), move |output| match output {
+                input::OutputMsg::Measure(measurements) => {
+                    OutputMsg::Measure(name(board, "IN 1"), measurements)
+                }
+                input::OutputMsg::Start => OutputMsg::Start(name(board, "IN 1")),
+                input::OutputMsg::Rescale => OutputMsg::Rescale,
+                input::OutputMsg::Stop => OutputMsg::Stop(name(board, "IN 1")),
             });
 
         let in2 = input::Model::builder()
-            .launch((init.clone(), redpitaya_scpi::acquire::Source::IN2))
-            .forward(sender.output_sender(), |output| match output {
-                input::OutputMsg::Start => OutputMsg::Start(redpitaya_scpi::acquire::Source::IN2),
-                input::OutputMsg::Stop => OutputMsg::Stop(redpitaya_scpi::acquire::Source::IN2),
+            .launch((
+                init.clone(),
+                redpitaya_scpi::acquire::Source::IN2,
+                name(board, "IN 2"),
+            ))
+            .forward(sender.output_sender(), move |output| match output {
+                input::OutputMsg::Measure(measurements) => {
+                    OutputMsg::Measure(name(board, "IN 2"), measurements)
+                }
+                input::OutputMsg::Start => OutputMsg::Start(name(board, "IN 2")),
+                input::OutputMsg::Rescale => OutputMsg::Rescale,
+                input::OutputMsg::Stop => OutputMsg::Stop(name(board, "IN 2")),
+            });
+
+        let sample_period = crate::measure::sample_period(decimation.into());
+
+        let math1 = math::Model::builder()
+            .launch((name(board, "MATH 1"), sample_period))
+            .forward(sender.output_sender(), move |output| match output {
+                math::OutputMsg::Measure(measurements) => {
+                    OutputMsg::Measure(name(board, "MATH 1"), measurements)
+                }
+                math::OutputMsg::Start => OutputMsg::Start(name(board, "MATH 1")),
+                math::OutputMsg::Stop => OutputMsg::Stop(name(board, "MATH 1")),
+            });
+
+        let math2 = math::Model::builder()
+            .launch((name(board, "MATH 2"), sample_period))
+            .forward(sender.output_sender(), move |output| match output {
+                math::OutputMsg::Measure(measurements) => {
+                    OutputMsg::Measure(name(board, "MATH 2"), measurements)
+                }
+                math::OutputMsg::Start => OutputMsg::Start(name(board, "MATH 2")),
+                math::OutputMsg::Stop => OutputMsg::Stop(name(board, "MATH 2")),
             });
 
         let model = Self {
+            average: init.is_average_enabled().unwrap_or_default(),
+            decimation,
+            export_dialog,
             rp: init,
             in1,
             in2,
+            math1,
+            math2,
             rate,
         };
 
@@ -85,20 +174,66 @@ impl relm4::SimpleComponent for Model {
     fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>) {
         match msg {
             InputMsg::Average(enable) => {
-                if enable {
-                    self.rp.enable_average();
-                } else {
-                    self.rp.disable_average();
-                }
+                self.average = enable;
+                self.rp.set_average(enable).ok();
+            }
+            InputMsg::ClearPersistence => {
+                self.in1.emit(input::InputMsg::ClearPersistence);
+                self.in2.emit(input::InputMsg::ClearPersistence);
+            }
+            InputMsg::Persistence(persistence) => {
+                self.in1.emit(input::InputMsg::Persistence(persistence));
+                self.in2.emit(input::InputMsg::Persistence(persistence));
+            }
+            InputMsg::Export => {
+                self.export_dialog
+                    .emit(relm4_components::save_dialog::SaveDialogMsg::SaveAs(
+                        "capture.csv".to_string(),
+                    ))
+            }
+            InputMsg::Reapply => {
+                self.rp.set_decimation(self.decimation).ok();
+                self.rp.set_average(self.average).ok();
+                self.in1.emit(input::InputMsg::Reapply);
+                self.in2.emit(input::InputMsg::Reapply);
+            }
+            InputMsg::Restore(acquire) => {
+                self.rate.emit(crate::widget::radio::InputMsg::Set(
+                    acquire.decimation.into(),
+                ));
+
+                self.average = acquire.average;
+                self.rp.set_average(acquire.average).ok();
+
+                self.in1.emit(input::InputMsg::Restore(acquire.in1));
+                self.in2.emit(input::InputMsg::Restore(acquire.in2));
+                self.math1.emit(math::InputMsg::Restore(acquire.math1));
+                self.math2.emit(math::InputMsg::Restore(acquire.math2));
             }
             InputMsg::Rate(rate) => {
-                self.rp.set_decimation(rate.into());
+                self.decimation = rate.into();
+                self.rp.set_decimation(self.decimation).ok();
+                self.in1.emit(input::InputMsg::Rate(rate));
+                self.in2.emit(input::InputMsg::Rate(rate));
+                self.math1.emit(math::InputMsg::Rate(rate));
+                self.math2.emit(math::InputMsg::Rate(rate));
                 sender.output(OutputMsg::Rate(rate)).ok();
             }
+            InputMsg::SetRate(rate) => {
+                self.rate.emit(crate::widget::radio::InputMsg::Set(rate));
+            }
             InputMsg::SetData(source, data) => {
-                self.input(source).emit(input::InputMsg::SetData(data))
+                let attenuation = self.input(source).model().attenuation() as f64;
+                let volts = data
+                    .iter()
+                    .map(|sample| sample * attenuation)
+                    .collect::<Vec<_>>();
+
+                self.math1
+                    .emit(math::InputMsg::SetData(source, volts.clone()));
+                self.math2.emit(math::InputMsg::SetData(source, volts));
+                self.input(source).emit(input::InputMsg::SetData(data));
             }
-            InputMsg::Redraw(context, model) => self.draw(context, model).unwrap(),
         };
     }
 
@@ -112,7 +247,7 @@ impl relm4::SimpleComponent for Model {
             gtk::CheckButton {
                 set_label: Some("Average"),
                 #[watch]
-                set_active: model.rp.is_average_enabled(),
+                set_active: model.average,
 
                 connect_toggled[sender] => move |this| {
                     sender.input(InputMsg::Average(this.is_active()));
@@ -120,26 +255,148 @@ impl relm4::SimpleComponent for Model {
             },
             append: model.in1.widget(),
             append: model.in2.widget(),
+            append: model.math1.widget(),
+            append: model.math2.widget(),
+
+            gtk::Button {
+                set_label: "Export",
+
+                connect_clicked[sender] => move |_| {
+                    sender.input(InputMsg::Export);
+                }
+            },
         }
     }
 }
 
 impl Model {
-    fn draw(
+    pub(super) fn draw(
         &self,
-        context: Box<gtk::cairo::Context>,
-        model: Box<crate::application::Data>,
+        context: &gtk::cairo::Context,
+        data: &crate::application::Data,
     ) -> Result<(), gtk::cairo::Error> {
         context.save()?;
-        self.in1
-            .emit(input::InputMsg::Redraw(context.clone(), model.clone()));
+        self.in1.model().draw(context, data)?;
         context.restore()?;
         context.save()?;
-        self.in2
-            .emit(input::InputMsg::Redraw(context.clone(), model));
+        self.in2.model().draw(context, data)?;
+        context.restore()?;
+        context.save()?;
+        self.math1.model().draw(context, data)?;
+        context.restore()?;
+        context.save()?;
+        self.math2.model().draw(context, data)?;
         context.restore()
     }
 
+    pub(super) fn axes(&self, data: &crate::application::Data) -> Vec<crate::application::Axis> {
+        [&self.in1, &self.in2]
+            .iter()
+            .filter_map(|input| input.model().axis(data))
+            .chain(
+                [&self.math1, &self.math2]
+                    .iter()
+                    .filter_map(|math| math.model().axis(data)),
+            )
+            .collect()
+    }
+
+    pub(super) fn xy(&self) -> Option<(Vec<f64>, Vec<f64>)> {
+        let (in1, in2) = (self.in1.model(), self.in2.model());
+        let (_, x) = in1.signal()?;
+        let (_, y) = in2.signal()?;
+
+        Some((
+            x.iter().map(|x| x / in1.scale()).collect(),
+            y.iter().map(|y| y / in2.scale()).collect(),
+        ))
+    }
+
+    pub(super) fn signals(&self) -> Vec<(String, Vec<f64>)> {
+        [&self.in1, &self.in2]
+            .iter()
+            .filter_map(|input| input.model().signal())
+            .chain(
+                [&self.math1, &self.math2]
+                    .iter()
+                    .filter_map(|math| math.model().signal()),
+            )
+            .collect()
+    }
+
+    pub(super) fn samples(
+        &self,
+        data: &crate::application::Data,
+        times: &[f64],
+    ) -> Vec<(String, Vec<f64>)> {
+        [&self.in1, &self.in2]
+            .iter()
+            .filter_map(|input| input.model().samples(data, times))
+            .chain(
+                [&self.math1, &self.math2]
+                    .iter()
+                    .filter_map(|math| math.model().samples(data, times)),
+            )
+            .collect()
+    }
+
+    pub(super) fn session(&self) -> crate::application::session::Acquire {
+        crate::application::session::Acquire {
+            decimation: self.decimation,
+            average: self.average,
+            in1: self.in1.model().session(),
+            in2: self.in2.model().session(),
+            math1: self.math1.model().session(),
+            math2: self.math2.model().session(),
+        }
+    }
+
+    pub(super) fn export(
+        &self,
+        path: &std::path::Path,
+        data: &crate::application::Data,
+    ) -> std::io::Result<()> {
+        let traces = [&self.in1, &self.in2]
+            .iter()
+            .filter_map(|input| input.model().trace(data))
+            .chain(
+                [&self.math1, &self.math2]
+                    .iter()
+                    .filter_map(|math| math.model().trace(data)),
+            )
+            .collect::<Vec<_>>();
+
+        let samples = traces
+            .iter()
+            .map(|(_, trace)| trace.len())
+            .max()
+            .unwrap_or_default()
+            .min(data.scales.n_samples as usize);
+        let time = (0..samples)
+            .map(|sample| data.scales.sample_to_ms(sample as u32))
+            .collect::<Vec<_>>();
+
+        let mut columns = vec![("time (µs)", time.as_slice())];
+        for (name, trace) in &traces {
+            columns.push((name, trace));
+        }
+
+        if path.extension().is_some_and(|extension| extension == "npy") {
+            crate::export::npy(path, &columns)
+        } else {
+            crate::export::csv(path, &columns)
+        }
+    }
+
+    pub(super) fn probe(&self, source: redpitaya_scpi::acquire::Source) -> (String, f64) {
+        let input = self.input(source).model();
+
+        (
+            input.name().to_string(),
+            input.attenuation() as f64 / input.scale(),
+        )
+    }
+
     fn input(&self, source: redpitaya_scpi::acquire::Source) -> &relm4::Controller<input::Model> {
         match source {
             redpitaya_scpi::acquire::Source::IN1 => &self.in1,
@@ -147,3 +404,11 @@ impl Model {
         }
     }
 }
+
+pub(super) fn name(board: usize, channel: &str) -> String {
+    if board == 0 {
+        channel.to_string()
+    } else {
+        format!("{channel} #{}", board + 1)
+    }
+}
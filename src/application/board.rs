@@ -0,0 +1,246 @@
+use gtk::prelude::*;
+use relm4::ComponentController as _;
+
+#[derive(Debug)]
+pub enum InputMsg {
+    Acquire(super::acquire::OutputMsg),
+    ClearPersistence,
+    Delay(u16),
+    Generator(super::generator::OutputMsg),
+    Level(f32),
+    Persistence(crate::persistence::Persistence),
+    Rate(redpitaya_scpi::acquire::SamplingRate),
+    Remove,
+    Restore(crate::application::session::Board),
+    Stop,
+    Trigger(super::trigger::OutputMsg),
+}
+
+#[derive(Debug)]
+pub enum OutputMsg {
+    Acquire(super::acquire::OutputMsg),
+    Draw,
+    Remove,
+}
+
+pub struct Model {
+    acquire: relm4::Controller<super::acquire::Model>,
+    delay: u16,
+    generator: relm4::Controller<super::generator::Model>,
+    level: f32,
+    link: super::link::Link,
+    trigger: relm4::Controller<super::trigger::Model>,
+}
+
+#[relm4::component(pub)]
+impl relm4::Component for Model {
+    type CommandOutput = super::link::Command;
+    type Init = (usize, String);
+    type Input = InputMsg;
+    type Output = OutputMsg;
+
+    fn init(
+        init: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        use relm4::Component as _;
+
+        let (board, addr) = init;
+        let redpitaya = crate::redpitaya::Redpitaya::new(addr.clone());
+
+        let acquire = super::acquire::Model::builder()
+            .launch((redpitaya.acquire.clone(), board))
+            .forward(sender.input_sender(), InputMsg::Acquire);
+
+        let generator = super::generator::Model::builder()
+            .launch((
+                redpitaya.generator.clone(),
+                redpitaya.burst.clone(),
+                redpitaya.socket().clone(),
+            ))
+            .forward(sender.input_sender(), InputMsg::Generator);
+
+        let trigger = super::trigger::Model::builder()
+            .launch(redpitaya.trigger.clone())
+            .forward(sender.input_sender(), InputMsg::Trigger);
+
+        let model = Self {
+            acquire,
+            delay: 0,
+            generator,
+            level: 0.0,
+            link: super::link::Link::new(redpitaya, addr, &sender),
+            trigger,
+        };
+
+        let widgets = view_output!();
+
+        model.update_connection(&widgets);
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>, _: &Self::Root) {
+        match msg {
+            InputMsg::Acquire(msg) => {
+                sender.output(OutputMsg::Acquire(msg)).ok();
+            }
+            InputMsg::ClearPersistence => self
+                .acquire
+                .emit(super::acquire::InputMsg::ClearPersistence),
+            InputMsg::Delay(delay) => {
+                self.delay = delay;
+                self.trigger.emit(super::trigger::InputMsg::Delay(delay));
+            }
+            InputMsg::Generator(_) => (),
+            InputMsg::Level(level) => {
+                self.level = level;
+                self.trigger.emit(super::trigger::InputMsg::Level(level));
+            }
+            InputMsg::Persistence(persistence) => self
+                .acquire
+                .emit(super::acquire::InputMsg::Persistence(persistence)),
+            InputMsg::Rate(rate) => self.acquire.emit(super::acquire::InputMsg::SetRate(rate)),
+            InputMsg::Remove => {
+                self.link.stop();
+                sender.output(OutputMsg::Remove).ok();
+            }
+            InputMsg::Restore(board) => {
+                self.acquire
+                    .emit(super::acquire::InputMsg::Restore(board.acquire));
+                self.generator
+                    .emit(super::generator::InputMsg::Restore(board.generator));
+            }
+            InputMsg::Stop => self.link.stop(),
+            InputMsg::Trigger(msg) => match msg {
+                super::trigger::OutputMsg::Channel => (),
+                super::trigger::OutputMsg::Normal => self.link.read(&sender, true),
+                super::trigger::OutputMsg::Auto | super::trigger::OutputMsg::Single => {
+                    self.link.read(&sender, false)
+                }
+            },
+        }
+    }
+
+    fn update_cmd_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::CommandOutput,
+        sender: relm4::ComponentSender<Self>,
+        _: &Self::Root,
+    ) {
+        match self.link.update(msg, &sender) {
+            Some(super::link::Event::Acquired(in1, in2)) => {
+                self.acquire.emit(super::acquire::InputMsg::SetData(
+                    redpitaya_scpi::acquire::Source::IN1,
+                    in1,
+                ));
+                self.acquire.emit(super::acquire::InputMsg::SetData(
+                    redpitaya_scpi::acquire::Source::IN2,
+                    in2,
+                ));
+
+                sender.output(OutputMsg::Draw).ok();
+            }
+            Some(super::link::Event::Connected) => {
+                self.acquire.emit(super::acquire::InputMsg::Reapply);
+                self.trigger
+                    .emit(super::trigger::InputMsg::Level(self.level));
+                self.trigger
+                    .emit(super::trigger::InputMsg::Delay(self.delay));
+                self.trigger.emit(super::trigger::InputMsg::Reapply);
+                self.generator.emit(super::generator::InputMsg::Reapply(
+                    redpitaya_scpi::generator::Source::OUT1,
+                ));
+                self.generator.emit(super::generator::InputMsg::Reapply(
+                    redpitaya_scpi::generator::Source::OUT2,
+                ));
+                self.update_connection(widgets);
+            }
+            Some(super::link::Event::Disconnected) => {
+                self.generator.emit(super::generator::InputMsg::Abort);
+                self.update_connection(widgets);
+            }
+            None => (),
+        }
+    }
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: 5,
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Horizontal,
+                set_spacing: 5,
+                set_margin_all: 5,
+
+                #[name = "connection"]
+                gtk::Label {
+                    set_hexpand: true,
+                    set_xalign: 0.0,
+                },
+                gtk::Button {
+                    set_label: "Remove",
+
+                    connect_clicked[sender] => move |_| {
+                        sender.input(InputMsg::Remove);
+                    }
+                },
+            },
+            #[name = "notebook"]
+            gtk::Notebook {
+                set_scrollable: true,
+                set_vexpand: true,
+
+                append_page: (model.acquire.widget(), Some(&gtk::Label::new(Some("Acquire")))),
+                append_page: (model.generator.widget(), Some(&gtk::Label::new(Some("Generator")))),
+                append_page: (model.trigger.widget(), Some(&gtk::Label::new(Some("Trigger")))),
+            },
+        }
+    }
+}
+
+impl Model {
+    pub(super) fn addr(&self) -> &str {
+        self.link.addr()
+    }
+
+    pub(super) fn session(&self) -> crate::application::session::Board {
+        crate::application::session::Board {
+            addr: self.addr().to_string(),
+            acquire: self.acquire.model().session(),
+            generator: self.generator.model().session(),
+        }
+    }
+
+    pub(super) fn draw(
+        &self,
+        context: &gtk::cairo::Context,
+        data: &super::Data,
+    ) -> Result<(), gtk::cairo::Error> {
+        self.acquire.model().draw(context, data)
+    }
+
+    pub(super) fn axes(&self, data: &super::Data) -> Vec<super::Axis> {
+        self.acquire.model().axes(data)
+    }
+
+    pub(super) fn signals(&self) -> Vec<(String, Vec<f64>)> {
+        self.acquire.model().signals()
+    }
+
+    pub(super) fn samples(&self, data: &super::Data, times: &[f64]) -> Vec<(String, Vec<f64>)> {
+        self.acquire.model().samples(data, times)
+    }
+
+    pub(super) fn export(&self, path: &std::path::Path, data: &super::Data) -> std::io::Result<()> {
+        self.acquire.model().export(path, data)
+    }
+
+    fn update_connection(&self, widgets: &ModelWidgets) {
+        widgets.connection.set_label(&self.link.label());
+        widgets.notebook.set_sensitive(self.link.is_connected());
+    }
+}
@@ -0,0 +1,541 @@
+use crate::color::Colorable as _;
+use gtk::prelude::*;
+
+#[derive(Debug)]
+pub enum Command {
+    Done,
+    Measure(usize),
+    Trigger,
+    Tune(usize),
+}
+
+#[derive(Debug)]
+pub enum InputMsg {
+    Abort,
+    Amplitude(f64),
+    Export,
+    ExportTo(std::path::PathBuf),
+    Points(usize),
+    Repaint,
+    Restore(crate::application::session::Bode),
+    Settle(u32),
+    Start(f64),
+    Stop(f64),
+    Toggle,
+}
+
+#[derive(Debug)]
+pub enum OutputMsg {
+    Finish,
+}
+
+pub struct Model {
+    frequencies: Vec<f64>,
+    handler: relm4::abstractions::DrawHandler,
+    points: Vec<crate::bode::Point>,
+    previous: Option<redpitaya_scpi::acquire::Decimation>,
+    rate: redpitaya_scpi::acquire::SamplingRate,
+    redpitaya: crate::redpitaya::Redpitaya,
+    running: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+    save_dialog: relm4::Controller<relm4_components::save_dialog::SaveDialog>,
+    settings: crate::application::session::Bode,
+    status: String,
+}
+
+#[relm4::component(pub)]
+impl relm4::Component for Model {
+    type CommandOutput = Command;
+    type Init = crate::redpitaya::Redpitaya;
+    type Input = InputMsg;
+    type Output = OutputMsg;
+
+    fn init(
+        init: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        use relm4::Component as _;
+
+        let csv = gtk::FileFilter::new();
+        csv.set_name(Some("CSV"));
+        csv.add_pattern("*.csv");
+
+        let save_dialog = relm4_components::save_dialog::SaveDialog::builder()
+            .transient_for_native(&root)
+            .launch(relm4_components::save_dialog::SaveDialogSettings {
+                filters: vec![csv],
+                ..Default::default()
+            })
+            .connect_receiver(gtk::glib::clone!(
+                #[strong]
+                sender,
+                move |_, response| {
+                    if let relm4_components::save_dialog::SaveDialogResponse::Accept(path) =
+                        response
+                    {
+                        sender.input(InputMsg::ExportTo(path));
+                    }
+                }
+            ));
+
+        let model = Self {
+            frequencies: Vec::new(),
+            handler: relm4::abstractions::DrawHandler::new(),
+            points: Vec::new(),
+            previous: None,
+            rate: redpitaya_scpi::acquire::SamplingRate::RATE_125MHz,
+            redpitaya: init,
+            running: None,
+            save_dialog,
+            settings: crate::application::session::Bode::default(),
+            status: String::new(),
+        };
+
+        let drawing_area = model.handler.drawing_area();
+        let widgets = view_output!();
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>, _: &Self::Root) {
+        match msg {
+            InputMsg::Abort => {
+                if self.running.is_some() {
+                    self.abort();
+                    self.status = "Connection lost".to_string();
+                }
+            }
+            InputMsg::Amplitude(amplitude) => self.settings.amplitude = amplitude,
+            InputMsg::Export => {
+                self.save_dialog
+                    .emit(relm4_components::save_dialog::SaveDialogMsg::SaveAs(
+                        "bode.csv".to_string(),
+                    ))
+            }
+            InputMsg::ExportTo(path) => {
+                if let Err(err) = crate::bode::export(&path, &self.points) {
+                    log::error!("Unable to export {}: {err}", path.display());
+                }
+            }
+            InputMsg::Points(points) => self.settings.points = points.max(2),
+            InputMsg::Repaint => (),
+            InputMsg::Restore(settings) => self.settings = settings,
+            InputMsg::Settle(settle) => self.settings.settle = settle,
+            InputMsg::Start(frequency) => self.settings.start = frequency,
+            InputMsg::Stop(frequency) => self.settings.stop = frequency,
+            InputMsg::Toggle => match self.running.take() {
+                Some(running) => {
+                    running.store(false, std::sync::atomic::Ordering::Relaxed);
+                    self.finish(&sender);
+                    self.status = "Stopped".to_string();
+                }
+                None => self.start(&sender),
+            },
+        }
+
+        self.repaint().ok();
+    }
+
+    fn update_cmd(
+        &mut self,
+        msg: Self::CommandOutput,
+        sender: relm4::ComponentSender<Self>,
+        _: &Self::Root,
+    ) {
+        if self.running.is_none() {
+            return;
+        }
+
+        match msg {
+            Command::Done => {
+                self.running = None;
+                self.finish(&sender);
+                self.status = "Done".to_string();
+            }
+            Command::Tune(step) => {
+                let frequency = self.frequencies[step];
+
+                self.rate = crate::bode::rate(frequency);
+                self.redpitaya.acquire.set_decimation(self.rate.into()).ok();
+                self.redpitaya
+                    .generator
+                    .set_frequency(redpitaya_scpi::generator::Source::OUT1, frequency as u32)
+                    .ok();
+                self.status = format!(
+                    "{}/{} - {}",
+                    step + 1,
+                    self.frequencies.len(),
+                    crate::scales::format(frequency, "Hz"),
+                );
+            }
+            Command::Trigger => {
+                self.redpitaya.acquire.start().ok();
+                self.redpitaya
+                    .trigger
+                    .enable(redpitaya_scpi::trigger::Source::NOW)
+                    .ok();
+            }
+            Command::Measure(step) => {
+                let data = &self.redpitaya.data;
+                let acquisition = data
+                    .read_all(redpitaya_scpi::acquire::Source::IN1)
+                    .and_then(|stimulus| {
+                        Ok((
+                            stimulus,
+                            data.read_all(redpitaya_scpi::acquire::Source::IN2)?,
+                        ))
+                    });
+
+                match acquisition {
+                    Ok((stimulus, response)) => {
+                        self.points.push(crate::bode::measure(
+                            self.frequencies[step],
+                            &stimulus,
+                            &response,
+                            crate::measure::sample_period(self.rate),
+                        ));
+                        self.repaint().ok();
+                    }
+                    Err(err) => {
+                        self.abort();
+                        self.status = format!("Unable to read the inputs: {err}");
+                    }
+                }
+            }
+        }
+    }
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: 5,
+            set_margin_all: 5,
+
+            #[local_ref]
+            drawing_area -> gtk::DrawingArea {
+                set_content_height: 300,
+                set_vexpand: true,
+
+                connect_resize[sender] => move |_, _, _| {
+                    sender.input(InputMsg::Repaint);
+                },
+            },
+            gtk::Grid {
+                #[watch]
+                set_sensitive: model.running.is_none(),
+                set_column_spacing: 5,
+                set_row_spacing: 5,
+
+                attach[0, 0, 1, 1] = &gtk::Label {
+                    set_label: "Start (Hz)",
+                    set_xalign: 0.0,
+                },
+                attach[1, 0, 1, 1] = &gtk::SpinButton::with_range(1.0, 62_500_000.0, 1.0) {
+                    set_hexpand: true,
+                    #[watch]
+                    set_value: model.settings.start,
+
+                    connect_value_changed[sender] => move |this| {
+                        sender.input(InputMsg::Start(this.value()));
+                    },
+                },
+                attach[0, 1, 1, 1] = &gtk::Label {
+                    set_label: "Stop (Hz)",
+                    set_xalign: 0.0,
+                },
+                attach[1, 1, 1, 1] = &gtk::SpinButton::with_range(1.0, 62_500_000.0, 1.0) {
+                    set_hexpand: true,
+                    #[watch]
+                    set_value: model.settings.stop,
+
+                    connect_value_changed[sender] => move |this| {
+                        sender.input(InputMsg::Stop(this.value()));
+                    },
+                },
+                attach[0, 2, 1, 1] = &gtk::Label {
+                    set_label: "Points",
+                    set_xalign: 0.0,
+                },
+                attach[1, 2, 1, 1] = &gtk::SpinButton::with_range(2.0, 1_000.0, 1.0) {
+                    set_hexpand: true,
+                    #[watch]
+                    set_value: model.settings.points as f64,
+
+                    connect_value_changed[sender] => move |this| {
+                        sender.input(InputMsg::Points(this.value() as usize));
+                    },
+                },
+                attach[0, 3, 1, 1] = &gtk::Label {
+                    set_label: "Amplitude (V)",
+                    set_xalign: 0.0,
+                },
+                attach[1, 3, 1, 1] = &gtk::SpinButton::with_range(0.01, 1.0, 0.01) {
+                    set_digits: 2,
+                    set_hexpand: true,
+                    #[watch]
+                    set_value: model.settings.amplitude,
+
+                    connect_value_changed[sender] => move |this| {
+                        sender.input(InputMsg::Amplitude(this.value()));
+                    },
+                },
+                attach[0, 4, 1, 1] = &gtk::Label {
+                    set_label: "Settle (ms)",
+                    set_xalign: 0.0,
+                },
+                attach[1, 4, 1, 1] = &gtk::SpinButton::with_range(0.0, 10_000.0, 10.0) {
+                    set_hexpand: true,
+                    #[watch]
+                    set_value: model.settings.settle as f64,
+
+                    connect_value_changed[sender] => move |this| {
+                        sender.input(InputMsg::Settle(this.value() as u32));
+                    },
+                },
+            },
+            gtk::ProgressBar {
+                set_show_text: true,
+                #[watch]
+                set_fraction: model.progress(),
+                #[watch]
+                set_text: Some(&model.status),
+            },
+            gtk::Box {
+                set_orientation: gtk::Orientation::Horizontal,
+                set_spacing: 5,
+                set_homogeneous: true,
+
+                gtk::Button {
+                    #[watch]
+                    set_label: if model.running.is_some() { "Stop" } else { "Start" },
+                    set_tooltip_text: Some("Sweeps OUT 1, IN 1 is the stimulus and IN 2 the response"),
+
+                    connect_clicked[sender] => move |_| {
+                        sender.input(InputMsg::Toggle);
+                    },
+                },
+                gtk::Button {
+                    set_label: "Export",
+                    #[watch]
+                    set_sensitive: !model.points.is_empty(),
+
+                    connect_clicked[sender] => move |_| {
+                        sender.input(InputMsg::Export);
+                    },
+                },
+            },
+        }
+    }
+}
+
+impl Model {
+    pub(super) fn session(&self) -> crate::application::session::Bode {
+        self.settings
+    }
+
+    fn progress(&self) -> f64 {
+        if self.frequencies.is_empty() {
+            0.0
+        } else {
+            self.points.len() as f64 / self.frequencies.len() as f64
+        }
+    }
+
+    fn start(&mut self, sender: &relm4::ComponentSender<Self>) {
+        self.previous = self.redpitaya.acquire.decimation().ok();
+
+        if let Err(err) = self.prepare() {
+            self.previous = None;
+            self.status = format!("Unable to configure OUT 1: {err}");
+            return;
+        }
+
+        self.frequencies = crate::bode::frequencies(
+            self.settings.start,
+            self.settings.stop,
+            self.settings.points,
+        );
+        self.points.clear();
+
+        let running = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let frequencies = self.frequencies.clone();
+        let settle = std::time::Duration::from_millis(self.settings.settle as u64);
+
+        self.running = Some(running.clone());
+
+        sender.command(move |out, shutdown| {
+            let fut = shutdown
+                .register(async move {
+                    for (step, frequency) in frequencies.into_iter().enumerate() {
+                        let duration = crate::bode::rate(frequency).buffer_duration();
+
+                        out.send(Command::Tune(step)).ok();
+                        tokio::time::sleep(settle).await;
+                        out.send(Command::Trigger).ok();
+                        tokio::time::sleep(duration + duration / 10).await;
+
+                        if !running.load(std::sync::atomic::Ordering::Relaxed) {
+                            return;
+                        }
+
+                        out.send(Command::Measure(step)).ok();
+                    }
+
+                    out.send(Command::Done).ok();
+                })
+                .drop_on_shutdown();
+
+            Box::pin(fut)
+        });
+    }
+
+    fn prepare(&self) -> std::io::Result<()> {
+        let generator = &self.redpitaya.generator;
+        let source = redpitaya_scpi::generator::Source::OUT1;
+
+        generator.set_form(source, redpitaya_scpi::generator::Form::SINE)?;
+        generator.set_amplitude(source, self.settings.amplitude as f32)?;
+        generator.set_offset(source, 0.0)?;
+        generator.start(source)
+    }
+
+    fn abort(&mut self) {
+        if let Some(running) = self.running.take() {
+            running.store(false, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        self.previous = None;
+    }
+
+    fn finish(&mut self, sender: &relm4::ComponentSender<Self>) {
+        if let Some(decimation) = self.previous.take() {
+            self.redpitaya.acquire.set_decimation(decimation).ok();
+        }
+
+        sender.output(OutputMsg::Finish).ok();
+    }
+
+    fn repaint(&mut self) -> Result<(), gtk::cairo::Error> {
+        let width = self.handler.drawing_area().width() as f64;
+        let height = self.handler.drawing_area().height() as f64;
+        let context = self.handler.get_context();
+
+        context.set_color(crate::color::BACKGROUND);
+        context.paint()?;
+
+        if width <= 0.0 || height <= 0.0 {
+            return Ok(());
+        }
+
+        let font_size = (height / 30.0).max(8.0);
+        let (left, right, bottom) = (font_size * 5.0, font_size * 4.0, font_size * 2.0);
+        let (plot_width, plot_height) = (width - left - right, height - bottom - font_size);
+
+        let (start, stop) = (
+            self.settings.start.min(self.settings.stop).max(1.0),
+            self.settings.start.max(self.settings.stop).max(1.0),
+        );
+        let (decade_start, decade_stop) = (
+            start.log10().floor(),
+            stop.log10().ceil().max(start.log10().floor() + 1.0),
+        );
+
+        let gains = self
+            .points
+            .iter()
+            .map(|point| point.gain)
+            .filter(|gain| gain.is_finite());
+        let top = (gains.clone().fold(0.0, f64::max) / 10.0).ceil() * 10.0;
+        let bottom_gain = (gains.fold(-40.0, f64::min) / 10.0).floor() * 10.0;
+
+        let to_x = |frequency: f64| {
+            left + (frequency.log10() - decade_start) / (decade_stop - decade_start) * plot_width
+        };
+        let to_y = |value: f64, (min, max): (f64, f64)| {
+            font_size + (max - value) / (max - min) * plot_height
+        };
+
+        context.select_font_face(
+            "monospace",
+            gtk::cairo::FontSlant::Normal,
+            gtk::cairo::FontWeight::Normal,
+        );
+        context.set_font_size(font_size);
+        context.set_line_width(1.0);
+
+        for decade in decade_start as i32..=decade_stop as i32 {
+            for multiple in 1..10 {
+                let frequency = 10_f64.powi(decade) * multiple as f64;
+
+                if frequency.log10() > decade_stop {
+                    break;
+                }
+
+                let x = to_x(frequency);
+
+                if multiple == 1 {
+                    context.set_color(crate::color::MAIN_SCALE);
+                    context.move_to(x, height - font_size / 2.0);
+                    context.show_text(&crate::scales::format(frequency, "Hz"))?;
+                } else {
+                    context.set_color(crate::color::SECONDARY_SCALE);
+                }
+
+                context.move_to(x, font_size);
+                context.line_to(x, font_size + plot_height);
+                context.stroke()?;
+            }
+        }
+
+        for i in 0..=8 {
+            let y = font_size + plot_height / 8.0 * i as f64;
+            let gain = top - (top - bottom_gain) / 8.0 * i as f64;
+            let phase = 180.0 - 45.0 * i as f64;
+
+            context.set_color(crate::color::SECONDARY_SCALE);
+            context.move_to(left, y);
+            context.line_to(left + plot_width, y);
+            context.stroke()?;
+
+            context.set_color(crate::color::IN2);
+            context.move_to(0.0, y + font_size / 3.0);
+            context.show_text(&format!("{gain:.0} dB"))?;
+
+            context.set_color(crate::color::MATH1);
+            context.move_to(left + plot_width + font_size / 2.0, y + font_size / 3.0);
+            context.show_text(&format!("{phase:.0}°"))?;
+        }
+
+        let curves = [
+            (
+                crate::color::IN2,
+                (bottom_gain, top),
+                self.points
+                    .iter()
+                    .map(|point| point.gain)
+                    .collect::<Vec<_>>(),
+            ),
+            (
+                crate::color::MATH1,
+                (-180.0, 180.0),
+                self.points.iter().map(|point| point.phase).collect(),
+            ),
+        ];
+
+        context.set_line_width(2.0);
+
+        for (color, range, values) in curves {
+            context.set_color(color);
+
+            for (point, value) in self.points.iter().zip(values) {
+                if value.is_finite() {
+                    context.line_to(to_x(point.frequency), to_y(value, range));
+                }
+            }
+
+            context.stroke()?;
+        }
+
+        Ok(())
+    }
+}
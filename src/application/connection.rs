@@ -0,0 +1,336 @@
+use gtk::prelude::*;
+
+#[derive(Debug)]
+pub enum Command {
+    Found(Result<Vec<crate::connection::Board>, String>),
+    Identified(String, Result<String, String>),
+}
+
+#[derive(Debug)]
+pub enum InputMsg {
+    Connect,
+    Discover,
+    Scan,
+    Select(usize),
+}
+
+pub struct Model {
+    addr: gtk::EntryBuffer,
+    application: Option<relm4::Controller<super::Model>>,
+    boards: Vec<crate::connection::Board>,
+    connecting: bool,
+    error: String,
+    recent: super::session::Recent,
+    searching: bool,
+    subnet: gtk::EntryBuffer,
+}
+
+#[relm4::component(pub)]
+impl relm4::Component for Model {
+    type CommandOutput = Command;
+    type Init = Option<String>;
+    type Input = InputMsg;
+    type Output = ();
+
+    fn init(
+        init: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        let recent = super::session::Recent::load(&super::session::Recent::default_path())
+            .unwrap_or_default();
+        let addr = init
+            .clone()
+            .or_else(|| recent.addresses.first().cloned())
+            .unwrap_or_default();
+
+        let model = Self {
+            addr: gtk::EntryBuffer::new(Some(addr)),
+            application: None,
+            boards: Vec::new(),
+            connecting: false,
+            error: String::new(),
+            recent,
+            searching: false,
+            subnet: gtk::EntryBuffer::new(Some("192.168.1.0/24")),
+        };
+
+        let widgets = view_output!();
+
+        model.refresh(&widgets);
+
+        if init.is_some() {
+            sender.input(InputMsg::Connect);
+        } else {
+            sender.input(InputMsg::Discover);
+        }
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>, _: &Self::Root) {
+        match msg {
+            InputMsg::Connect => {
+                if self.connecting {
+                    return;
+                }
+
+                let addr = self.addr.text().to_string();
+
+                self.connecting = true;
+                self.error.clear();
+
+                sender.spawn_oneshot_command(move || {
+                    let identity =
+                        crate::connection::identify(&addr).map_err(|err| err.to_string());
+
+                    Command::Identified(addr, identity)
+                });
+            }
+            InputMsg::Discover => {
+                if self.searching {
+                    return;
+                }
+
+                self.searching = true;
+                self.error.clear();
+
+                sender.spawn_oneshot_command(|| {
+                    Command::Found(crate::connection::discover().map_err(|err| err.to_string()))
+                });
+            }
+            InputMsg::Scan => {
+                if self.searching {
+                    return;
+                }
+
+                let subnet = self.subnet.text().to_string();
+
+                self.searching = true;
+                self.error.clear();
+
+                sender.spawn_oneshot_command(move || {
+                    Command::Found(crate::connection::scan(&subnet).map_err(|err| err.to_string()))
+                });
+            }
+            InputMsg::Select(index) => {
+                if let Some(board) = self.boards().get(index) {
+                    self.addr.set_text(&board.addr);
+                    sender.input(InputMsg::Connect);
+                }
+            }
+        }
+    }
+
+    fn update_cmd_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::CommandOutput,
+        sender: relm4::ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        use relm4::Component as _;
+
+        match msg {
+            Command::Found(Ok(boards)) => {
+                self.searching = false;
+
+                for board in boards {
+                    match self
+                        .boards
+                        .iter_mut()
+                        .find(|known| known.addr == board.addr)
+                    {
+                        Some(known) => {
+                            known.hostname = board.hostname.or(known.hostname.take());
+                            known.identity = board.identity;
+                        }
+                        None => self.boards.push(board),
+                    }
+                }
+            }
+            Command::Found(Err(err)) => {
+                self.searching = false;
+                self.error = err;
+            }
+            Command::Identified(addr, Ok(identity)) => {
+                log::info!("Connected to {identity} at {addr}");
+
+                self.connecting = false;
+                self.recent.push(&addr);
+
+                let path = super::session::Recent::default_path();
+                if let Err(err) = self.recent.save(&path) {
+                    log::error!("Unable to save {}: {err}", path.display());
+                }
+
+                let application = super::Model::builder()
+                    .launch((crate::redpitaya::Redpitaya::new(addr.clone()), addr))
+                    .detach();
+                let window = application.widget();
+
+                relm4::main_application().add_window(window);
+                window.present();
+                root.set_visible(false);
+
+                self.application = Some(application);
+            }
+            Command::Identified(addr, Err(err)) => {
+                self.connecting = false;
+                self.error = format!("Unable to connect to {addr}: {err}");
+                root.present();
+            }
+        }
+
+        self.refresh(widgets);
+        self.update_view(widgets, sender);
+    }
+
+    view! {
+        gtk::Window {
+            set_title: Some(env!("CARGO_PKG_NAME")),
+            set_default_width: 400,
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 10,
+                set_margin_all: 10,
+
+                gtk::Label {
+                    set_label: "Boards",
+                    set_xalign: 0.0,
+                },
+                gtk::ScrolledWindow {
+                    set_min_content_height: 150,
+                    set_vexpand: true,
+
+                    #[name = "list"]
+                    gtk::ListBox {
+                        connect_row_activated[sender] => move |_, row| {
+                            sender.input(InputMsg::Select(row.index() as usize));
+                        },
+                    },
+                },
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Horizontal,
+                    set_spacing: 5,
+
+                    gtk::Button {
+                        set_label: "Discover",
+                        set_tooltip_text: Some("Look for boards announced with mDNS"),
+                        #[watch]
+                        set_sensitive: !model.searching,
+
+                        connect_clicked[sender] => move |_| {
+                            sender.input(InputMsg::Discover);
+                        },
+                    },
+                    gtk::Entry {
+                        set_buffer: &model.subnet,
+                        set_hexpand: true,
+                        set_placeholder_text: Some("192.168.1.0/24"),
+
+                        connect_activate[sender] => move |_| {
+                            sender.input(InputMsg::Scan);
+                        },
+                    },
+                    gtk::Button {
+                        set_label: "Scan",
+                        set_tooltip_text: Some("Look for boards answering on port 5000"),
+                        #[watch]
+                        set_sensitive: !model.searching,
+
+                        connect_clicked[sender] => move |_| {
+                            sender.input(InputMsg::Scan);
+                        },
+                    },
+                    gtk::Spinner {
+                        #[watch]
+                        set_spinning: model.searching,
+                    },
+                },
+                gtk::Label {
+                    set_label: "Red Pitaya address",
+                    set_xalign: 0.0,
+                },
+                gtk::Entry {
+                    set_buffer: &model.addr,
+                    set_placeholder_text: Some("rp-xxxxxx.local:5000"),
+                    #[watch]
+                    set_sensitive: !model.connecting,
+
+                    connect_activate[sender] => move |_| {
+                        sender.input(InputMsg::Connect);
+                    },
+                },
+                gtk::Label {
+                    add_css_class: "error",
+                    set_wrap: true,
+                    set_xalign: 0.0,
+                    #[watch]
+                    set_label: &model.error,
+                    #[watch]
+                    set_visible: !model.error.is_empty(),
+                },
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Horizontal,
+                    set_spacing: 5,
+                    set_halign: gtk::Align::End,
+
+                    gtk::Spinner {
+                        #[watch]
+                        set_spinning: model.connecting,
+                    },
+                    gtk::Button {
+                        set_label: "Connect",
+                        #[watch]
+                        set_sensitive: !model.connecting,
+
+                        connect_clicked[sender] => move |_| {
+                            sender.input(InputMsg::Connect);
+                        },
+                    },
+                },
+            },
+        }
+    }
+}
+
+impl Model {
+    fn boards(&self) -> Vec<crate::connection::Board> {
+        let mut boards = self.boards.clone();
+
+        for addr in &self.recent.addresses {
+            if !boards.iter().any(|board| &board.addr == addr) {
+                boards.push(crate::connection::Board {
+                    addr: addr.clone(),
+                    hostname: None,
+                    identity: None,
+                });
+            }
+        }
+
+        boards
+    }
+
+    fn refresh(&self, widgets: &ModelWidgets) {
+        while let Some(child) = widgets.list.first_child() {
+            widgets.list.remove(&child);
+        }
+
+        for board in self.boards() {
+            let label = gtk::Label::new(Some(&board.to_string()));
+            label.set_xalign(0.0);
+            label.set_margin_top(5);
+            label.set_margin_bottom(5);
+            label.set_margin_start(5);
+            label.set_margin_end(5);
+
+            if board.identity.is_none() {
+                label.set_tooltip_text(Some("Recently used"));
+            }
+
+            widgets.list.append(&label);
+        }
+    }
+}
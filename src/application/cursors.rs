@@ -0,0 +1,133 @@
+use crate::color::Colorable as _;
+use gtk::prelude::*;
+
+#[derive(Debug)]
+pub enum InputMsg {
+    Readout(String),
+    Time(bool),
+    Voltage(bool),
+}
+
+#[derive(Debug)]
+pub enum OutputMsg {
+    Time(bool),
+    Voltage(bool),
+}
+
+pub struct Model {
+    readout: String,
+    time: bool,
+    voltage: bool,
+}
+
+#[relm4::component(pub)]
+impl relm4::SimpleComponent for Model {
+    type Init = ();
+    type Input = InputMsg;
+    type Output = OutputMsg;
+
+    fn init(
+        _: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        let model = Self {
+            readout: String::new(),
+            time: false,
+            voltage: false,
+        };
+
+        let widgets = view_output!();
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>) {
+        match msg {
+            InputMsg::Readout(readout) => self.readout = readout,
+            InputMsg::Time(enable) => {
+                self.time = enable;
+                sender.output(OutputMsg::Time(enable)).ok();
+            }
+            InputMsg::Voltage(enable) => {
+                self.voltage = enable;
+                sender.output(OutputMsg::Voltage(enable)).ok();
+            }
+        }
+    }
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: 10,
+
+            gtk::CheckButton {
+                set_label: Some("Time cursors"),
+
+                connect_toggled[sender] => move |this| {
+                    sender.input(InputMsg::Time(this.is_active()));
+                },
+            },
+            gtk::CheckButton {
+                set_label: Some("Voltage cursors"),
+
+                connect_toggled[sender] => move |this| {
+                    sender.input(InputMsg::Voltage(this.is_active()));
+                },
+            },
+            gtk::Label {
+                add_css_class: "monospace",
+                set_halign: gtk::Align::Start,
+                set_selectable: true,
+                #[watch]
+                set_label: &model.readout,
+            },
+        },
+    }
+}
+
+impl Model {
+    pub(super) fn time(&self) -> bool {
+        self.time
+    }
+
+    pub(super) fn voltage(&self) -> bool {
+        self.voltage
+    }
+
+    pub(super) fn draw(
+        &self,
+        context: &gtk::cairo::Context,
+        data: &crate::application::Data,
+    ) -> Result<(), gtk::cairo::Error> {
+        context.set_color(crate::color::CURSOR);
+        context.set_dash(&[data.scales.width() / 200.0], 0.0);
+
+        if self.time {
+            context.set_line_width(data.scales.width() / 1000.0);
+
+            for name in ["T1", "T2"] {
+                let x = data.offset(name);
+
+                context.move_to(x, data.scales.v.0);
+                context.line_to(x, data.scales.v.1);
+                context.stroke()?;
+            }
+        }
+
+        if self.voltage {
+            context.set_dash(&[data.scales.height() / 200.0], 0.0);
+            context.set_line_width(data.scales.height() / 1000.0);
+
+            for name in ["V1", "V2"] {
+                let y = data.offset(name);
+
+                context.move_to(data.scales.h.0, y);
+                context.line_to(data.scales.h.1, y);
+                context.stroke()?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,250 @@
+use crate::color::Colorable as _;
+use gtk::prelude::*;
+
+#[derive(Debug)]
+pub enum InputMsg {
+    DrawBegin(f64, f64),
+    DrawEnd,
+    DrawUpdate(f64, f64),
+    Expression(String),
+    Load,
+    Open(std::path::PathBuf),
+    Repaint,
+    Set(Vec<f64>),
+}
+
+#[derive(Debug)]
+pub enum OutputMsg {
+    Change(Vec<f64>),
+}
+
+pub struct Model {
+    color: crate::Color,
+    error: String,
+    handler: relm4::abstractions::DrawHandler,
+    open_dialog: relm4::Controller<relm4_components::open_dialog::OpenDialog>,
+    pointer: Option<((f64, f64), usize)>,
+    waveform: Vec<f64>,
+}
+
+#[relm4::component(pub)]
+impl relm4::SimpleComponent for Model {
+    type Init = crate::Color;
+    type Input = InputMsg;
+    type Output = OutputMsg;
+
+    fn init(
+        init: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        use relm4::Component as _;
+
+        let samples = gtk::FileFilter::new();
+        samples.set_name(Some("Samples"));
+        samples.add_pattern("*.csv");
+        samples.add_pattern("*.npy");
+        samples.add_pattern("*.wav");
+
+        let open_dialog = relm4_components::open_dialog::OpenDialog::builder()
+            .transient_for_native(&root)
+            .launch(relm4_components::open_dialog::OpenDialogSettings {
+                filters: vec![samples],
+                ..Default::default()
+            })
+            .connect_receiver(gtk::glib::clone!(
+                #[strong]
+                sender,
+                move |_, response| {
+                    if let relm4_components::open_dialog::OpenDialogResponse::Accept(path) =
+                        response
+                    {
+                        sender.input(InputMsg::Open(path));
+                    }
+                }
+            ));
+
+        let model = Self {
+            color: init,
+            error: String::new(),
+            handler: relm4::abstractions::DrawHandler::new(),
+            open_dialog,
+            pointer: None,
+            waveform: vec![0.0; crate::waveform::LEN],
+        };
+
+        let drawing_area = model.handler.drawing_area();
+        let widgets = view_output!();
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>) {
+        match msg {
+            InputMsg::DrawBegin(x, y) => {
+                let index = self.index(x);
+
+                self.pointer = Some(((x, y), index));
+                self.waveform[index] = self.value(y);
+            }
+            InputMsg::DrawUpdate(dx, dy) => {
+                if let Some(((x, y), previous)) = self.pointer {
+                    let index = self.index(x + dx);
+                    let (from, to) = (self.waveform[previous], self.value(y + dy));
+                    let (start, end) = (previous.min(index), previous.max(index));
+
+                    for i in start..=end {
+                        let t = if end == start {
+                            1.0
+                        } else {
+                            (i as f64 - previous as f64) / (index as f64 - previous as f64)
+                        };
+
+                        self.waveform[i] = from + (to - from) * t;
+                    }
+
+                    self.pointer = Some(((x, y), index));
+                }
+            }
+            InputMsg::DrawEnd => {
+                self.pointer = None;
+                self.error.clear();
+                sender.output(OutputMsg::Change(self.waveform.clone())).ok();
+            }
+            InputMsg::Expression(expression) => {
+                match expression.parse::<crate::expression::Expression>() {
+                    Ok(expression)
+                        if expression
+                            .variables()
+                            .iter()
+                            .all(|variable| variable == "x") =>
+                    {
+                        self.error.clear();
+                        self.change(crate::waveform::expression(&expression), &sender);
+                    }
+                    Ok(_) => self.error = "Only the x variable is allowed".to_string(),
+                    Err(err) => self.error = err,
+                }
+            }
+            InputMsg::Load => self
+                .open_dialog
+                .emit(relm4_components::open_dialog::OpenDialogMsg::Open),
+            InputMsg::Open(path) => match crate::waveform::load(&path) {
+                Ok(waveform) => {
+                    self.error.clear();
+                    self.change(waveform, &sender);
+                }
+                Err(err) => self.error = format!("Unable to load {}: {err}", path.display()),
+            },
+            InputMsg::Repaint => (),
+            InputMsg::Set(waveform) => {
+                if !waveform.is_empty() {
+                    self.waveform = crate::waveform::resample(&waveform);
+                }
+            }
+        }
+
+        self.repaint().ok();
+    }
+
+    view! {
+        gtk::Frame {
+            set_label: Some("Arbitrary"),
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 5,
+
+                #[local_ref]
+                drawing_area -> gtk::DrawingArea {
+                    set_content_height: 120,
+                    set_tooltip_text: Some("Drag to draw the waveform"),
+
+                    connect_resize[sender] => move |_, _, _| {
+                        sender.input(InputMsg::Repaint);
+                    },
+
+                    add_controller = gtk::GestureDrag {
+                        connect_drag_begin[sender] => move |_, x, y| {
+                            sender.input(InputMsg::DrawBegin(x, y));
+                        },
+                        connect_drag_update[sender] => move |_, dx, dy| {
+                            sender.input(InputMsg::DrawUpdate(dx, dy));
+                        },
+                        connect_drag_end[sender] => move |_, _, _| {
+                            sender.input(InputMsg::DrawEnd);
+                        },
+                    },
+                },
+                gtk::Entry {
+                    set_placeholder_text: Some("sin(2*pi*x), x from 0 to 1"),
+
+                    connect_activate[sender] => move |this| {
+                        sender.input(InputMsg::Expression(this.text().to_string()));
+                    },
+                },
+                gtk::Button {
+                    set_label: "Load CSV, WAV or NumPy file",
+
+                    connect_clicked[sender] => move |_| {
+                        sender.input(InputMsg::Load);
+                    },
+                },
+                gtk::Label {
+                    add_css_class: "error",
+                    set_wrap: true,
+                    #[watch]
+                    set_label: &model.error,
+                    #[watch]
+                    set_visible: !model.error.is_empty(),
+                },
+            },
+        }
+    }
+}
+
+impl Model {
+    fn change(&mut self, waveform: Vec<f64>, sender: &relm4::ComponentSender<Self>) {
+        self.waveform = waveform;
+        sender.output(OutputMsg::Change(self.waveform.clone())).ok();
+    }
+
+    fn index(&self, x: f64) -> usize {
+        let width = self.handler.drawing_area().width().max(1) as f64;
+        let index = x / width * self.waveform.len() as f64;
+
+        (index.max(0.0) as usize).min(self.waveform.len() - 1)
+    }
+
+    fn value(&self, y: f64) -> f64 {
+        let height = self.handler.drawing_area().height().max(1) as f64;
+
+        (1.0 - 2.0 * y / height).clamp(-1.0, 1.0)
+    }
+
+    fn repaint(&mut self) -> Result<(), gtk::cairo::Error> {
+        let width = self.handler.drawing_area().width() as f64;
+        let height = self.handler.drawing_area().height() as f64;
+        let context = self.handler.get_context();
+
+        context.set_color(crate::color::BACKGROUND);
+        context.paint()?;
+
+        context.set_color(crate::color::SECONDARY_SCALE);
+        context.set_line_width(1.0);
+        context.move_to(0.0, height / 2.0);
+        context.line_to(width, height / 2.0);
+        context.stroke()?;
+
+        context.set_color(self.color.clone());
+
+        for (i, value) in self.waveform.iter().enumerate() {
+            let x = i as f64 * width / self.waveform.len() as f64;
+            let y = (1.0 - value) * height / 2.0;
+
+            context.line_to(x, y);
+        }
+
+        context.stroke()
+    }
+}
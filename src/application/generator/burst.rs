@@ -0,0 +1,234 @@
+use gtk::prelude::*;
+use relm4::ComponentController as _;
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Trigger {
+    #[default]
+    Immediate,
+    ExternalRising,
+    ExternalFalling,
+}
+
+impl std::fmt::Display for Trigger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match *self {
+            Self::Immediate => "Immediate",
+            Self::ExternalRising => "EXT rising",
+            Self::ExternalFalling => "EXT falling",
+        };
+
+        f.write_str(s)
+    }
+}
+
+impl From<Trigger> for redpitaya_scpi::generator::TriggerSource {
+    fn from(trigger: Trigger) -> Self {
+        match trigger {
+            Trigger::Immediate => Self::INT,
+            Trigger::ExternalRising => Self::EXT_PE,
+            Trigger::ExternalFalling => Self::EXT_NE,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputMsg {
+    Count(u32),
+    Enable(bool),
+    Fire,
+    Period(u32),
+    Repetitions(u32),
+    Restore(crate::application::session::Burst),
+    Trigger(Trigger),
+}
+
+#[derive(Debug)]
+pub enum OutputMsg {
+    Change(crate::application::session::Burst),
+}
+
+pub struct Model {
+    burst: crate::redpitaya::Burst,
+    generator: crate::redpitaya::Generator,
+    settings: crate::application::session::Burst,
+    source: redpitaya_scpi::generator::Source,
+    trigger: relm4::Controller<crate::widget::RadioGroup<Trigger>>,
+}
+
+#[relm4::component(pub)]
+impl relm4::SimpleComponent for Model {
+    type Init = (
+        crate::redpitaya::Generator,
+        crate::redpitaya::Burst,
+        redpitaya_scpi::generator::Source,
+    );
+    type Input = InputMsg;
+    type Output = OutputMsg;
+
+    fn init(
+        init: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        use relm4::Component as _;
+
+        let (generator, burst, source) = init;
+
+        let trigger = crate::widget::RadioGroup::builder()
+            .launch(crate::widget::radio::Options {
+                options: vec![
+                    Trigger::Immediate,
+                    Trigger::ExternalRising,
+                    Trigger::ExternalFalling,
+                ],
+                current: Some(Trigger::Immediate),
+                label: "Trigger",
+            })
+            .forward(sender.input_sender(), |output| {
+                let crate::widget::radio::OutputMsg::Change(trigger) = output;
+                InputMsg::Trigger(trigger)
+            });
+
+        let model = Self {
+            burst,
+            generator,
+            settings: crate::application::session::Burst::default(),
+            source,
+            trigger,
+        };
+
+        let widgets = view_output!();
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>) {
+        match msg {
+            InputMsg::Count(count) => self.settings.count = count,
+            InputMsg::Enable(enable) => self.settings.enable = enable,
+            InputMsg::Fire => {
+                self.generator.trigger(self.source).ok();
+                return;
+            }
+            InputMsg::Period(period) => self.settings.period = period,
+            InputMsg::Repetitions(repetitions) => self.settings.repetitions = repetitions,
+            InputMsg::Restore(settings) => {
+                self.settings = settings;
+                self.trigger
+                    .emit(crate::widget::radio::InputMsg::Set(settings.trigger));
+            }
+            InputMsg::Trigger(trigger) => self.settings.trigger = trigger,
+        }
+
+        self.apply().ok();
+        sender.output(OutputMsg::Change(self.settings)).ok();
+    }
+
+    view! {
+        gtk::Frame {
+            set_label: Some("Burst"),
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 5,
+
+                gtk::CheckButton {
+                    set_label: Some("Enable"),
+                    #[watch]
+                    set_active: model.settings.enable,
+
+                    connect_toggled[sender] => move |this| {
+                        sender.input(InputMsg::Enable(this.is_active()));
+                    },
+                },
+                gtk::Grid {
+                    #[watch]
+                    set_sensitive: model.settings.enable,
+                    set_column_spacing: 5,
+                    set_row_spacing: 5,
+
+                    attach[0, 0, 1, 1] = &gtk::Label {
+                        set_label: "Cycles",
+                        set_xalign: 0.0,
+                    },
+                    attach[1, 0, 1, 1] = &gtk::SpinButton::with_range(1.0, 50_000.0, 1.0) {
+                        set_hexpand: true,
+                        #[watch]
+                        set_value: model.settings.count as f64,
+
+                        connect_value_changed[sender] => move |this| {
+                            sender.input(InputMsg::Count(this.value() as u32));
+                        },
+                    },
+                    attach[0, 1, 1, 1] = &gtk::Label {
+                        set_label: "Repetitions",
+                        set_xalign: 0.0,
+                    },
+                    attach[1, 1, 1, 1] = &gtk::SpinButton::with_range(1.0, 50_000.0, 1.0) {
+                        set_hexpand: true,
+                        #[watch]
+                        set_value: model.settings.repetitions as f64,
+
+                        connect_value_changed[sender] => move |this| {
+                            sender.input(InputMsg::Repetitions(this.value() as u32));
+                        },
+                    },
+                    attach[0, 2, 1, 1] = &gtk::Label {
+                        set_label: "Period (µs)",
+                        set_xalign: 0.0,
+                    },
+                    attach[1, 2, 1, 1] = &gtk::SpinButton::with_range(1.0, 500_000_000.0, 1.0) {
+                        set_hexpand: true,
+                        #[watch]
+                        set_value: model.settings.period as f64,
+
+                        connect_value_changed[sender] => move |this| {
+                            sender.input(InputMsg::Period(this.value() as u32));
+                        },
+                    },
+                },
+                gtk::Box {
+                    #[watch]
+                    set_sensitive: model.settings.enable,
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_spacing: 5,
+
+                    append: model.trigger.widget(),
+
+                    gtk::Button {
+                        set_label: "Trigger",
+
+                        connect_clicked[sender] => move |_| {
+                            sender.input(InputMsg::Fire);
+                        },
+                    },
+                },
+            },
+        }
+    }
+}
+
+impl Model {
+    fn apply(&self) -> std::io::Result<()> {
+        let source = match self.source {
+            redpitaya_scpi::generator::Source::OUT1 => redpitaya_scpi::burst::Source::OUT1,
+            redpitaya_scpi::generator::Source::OUT2 => redpitaya_scpi::burst::Source::OUT2,
+        };
+
+        if self.settings.enable {
+            self.burst
+                .set_mode(source, redpitaya_scpi::burst::Mode::BURST)?;
+            self.burst.set_count(source, self.settings.count)?;
+            self.burst
+                .set_repetitions(source, self.settings.repetitions)?;
+            self.burst.set_period(source, self.settings.period)?;
+            self.generator
+                .set_trigger_source(self.source, self.settings.trigger.into())
+        } else {
+            self.burst
+                .set_mode(source, redpitaya_scpi::burst::Mode::CONTINUOUS)?;
+            self.generator
+                .set_trigger_source(self.source, redpitaya_scpi::generator::TriggerSource::INT)
+        }
+    }
+}
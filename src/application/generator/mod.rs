@@ -1,11 +1,21 @@
+mod arbitrary;
+pub(super) mod burst;
+pub(super) mod modulation;
 mod output;
+pub(super) mod sweep;
 
 use gtk::prelude::*;
 use relm4::ComponentController as _;
 
 #[derive(Debug)]
 pub enum InputMsg {
-    Redraw(Box<gtk::cairo::Context>, Box<crate::application::Data>),
+    Abort,
+    Link(bool),
+    Reapply(redpitaya_scpi::generator::Source),
+    Restart,
+    Restore(crate::application::session::Generator),
+    Start(redpitaya_scpi::generator::Source),
+    Stop(redpitaya_scpi::generator::Source),
 }
 
 #[derive(Debug)]
@@ -15,13 +25,19 @@ pub enum OutputMsg {
 }
 
 pub struct Model {
+    link: bool,
     out1: relm4::Controller<output::Model>,
     out2: relm4::Controller<output::Model>,
+    socket: crate::connection::Socket,
 }
 
 #[relm4::component(pub)]
 impl relm4::SimpleComponent for Model {
-    type Init = redpitaya_scpi::generator::Generator;
+    type Init = (
+        crate::redpitaya::Generator,
+        crate::redpitaya::Burst,
+        crate::connection::Socket,
+    );
     type Input = InputMsg;
     type Output = OutputMsg;
 
@@ -32,35 +48,83 @@ impl relm4::SimpleComponent for Model {
     ) -> relm4::ComponentParts<Self> {
         use relm4::Component as _;
 
+        let (generator, burst, socket) = init;
+
         let out1 = output::Model::builder()
-            .launch((init.clone(), redpitaya_scpi::generator::Source::OUT1))
-            .forward(sender.output_sender(), |output| match output {
+            .launch((
+                generator.clone(),
+                burst.clone(),
+                redpitaya_scpi::generator::Source::OUT1,
+            ))
+            .forward(sender.input_sender(), |output| match output {
                 output::OutputMsg::Start => {
-                    OutputMsg::Start(redpitaya_scpi::generator::Source::OUT1)
+                    InputMsg::Start(redpitaya_scpi::generator::Source::OUT1)
                 }
-                output::OutputMsg::Stop => OutputMsg::Stop(redpitaya_scpi::generator::Source::OUT1),
+                output::OutputMsg::Stop => InputMsg::Stop(redpitaya_scpi::generator::Source::OUT1),
             });
 
         let out2 = output::Model::builder()
-            .launch((init, redpitaya_scpi::generator::Source::OUT2))
-            .forward(sender.output_sender(), |output| match output {
+            .launch((generator, burst, redpitaya_scpi::generator::Source::OUT2))
+            .forward(sender.input_sender(), |output| match output {
                 output::OutputMsg::Start => {
-                    OutputMsg::Start(redpitaya_scpi::generator::Source::OUT2)
+                    InputMsg::Start(redpitaya_scpi::generator::Source::OUT2)
                 }
-                output::OutputMsg::Stop => OutputMsg::Stop(redpitaya_scpi::generator::Source::OUT2),
+                output::OutputMsg::Stop => InputMsg::Stop(redpitaya_scpi::generator::Source::OUT2),
             });
 
-        let model = Self { out1, out2 };
+        let model = Self {
+            link: false,
+            out1,
+            out2,
+            socket,
+        };
 
         let widgets = view_output!();
 
         relm4::ComponentParts { model, widgets }
     }
 
-    fn update(&mut self, msg: Self::Input, _: relm4::ComponentSender<Self>) {
-        let InputMsg::Redraw(context, model) = msg;
+    fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>) {
+        match msg {
+            InputMsg::Abort => {
+                self.out1.emit(output::InputMsg::Abort);
+                self.out2.emit(output::InputMsg::Abort);
+            }
+            InputMsg::Link(link) => {
+                self.link = link;
+
+                if link && (self.out1.model().is_started() || self.out2.model().is_started()) {
+                    self.out1.emit(output::InputMsg::Enable(true));
+                    self.out2.emit(output::InputMsg::Enable(true));
+                    self.synchronize();
+                }
+            }
+            InputMsg::Reapply(source) => self.output(source).emit(output::InputMsg::Reapply),
+            InputMsg::Restart => {
+                self.synchronize();
+            }
+            InputMsg::Restore(generator) => {
+                self.link = generator.link;
+                self.out1.emit(output::InputMsg::Restore(generator.out1));
+                self.out2.emit(output::InputMsg::Restore(generator.out2));
+                sender.input(InputMsg::Link(generator.link));
+            }
+            InputMsg::Start(source) => {
+                if self.link {
+                    self.other(source).emit(output::InputMsg::Enable(true));
+                    self.synchronize();
+                }
 
-        self.draw(context, model).unwrap();
+                sender.output(OutputMsg::Start(source)).ok();
+            }
+            InputMsg::Stop(source) => {
+                if self.link {
+                    self.other(source).emit(output::InputMsg::Enable(false));
+                }
+
+                sender.output(OutputMsg::Stop(source)).ok();
+            }
+        }
     }
 
     view! {
@@ -68,6 +132,31 @@ impl relm4::SimpleComponent for Model {
             set_orientation: gtk::Orientation::Vertical,
             set_spacing: 10,
 
+            gtk::Box {
+                set_orientation: gtk::Orientation::Horizontal,
+                set_spacing: 5,
+
+                gtk::CheckButton {
+                    set_label: Some("Link outputs"),
+                    set_tooltip_text: Some("Start and stop both outputs together, in phase"),
+                    #[watch]
+                    set_active: model.link,
+
+                    connect_toggled[sender] => move |this| {
+                        sender.input(InputMsg::Link(this.is_active()));
+                    },
+                },
+                gtk::Button {
+                    set_label: "Restart",
+                    set_hexpand: true,
+                    set_tooltip_text: Some("Restart both outputs simultaneously"),
+
+                    connect_clicked[sender] => move |_| {
+                        sender.input(InputMsg::Restart);
+                    },
+                },
+            },
+
             append: model.out1.widget(),
             append: model.out2.widget(),
         }
@@ -75,18 +164,87 @@ impl relm4::SimpleComponent for Model {
 }
 
 impl Model {
-    fn draw(
+    pub(super) fn session(&self) -> crate::application::session::Generator {
+        crate::application::session::Generator {
+            out1: self.out1.model().session(),
+            out2: self.out2.model().session(),
+            link: self.link,
+        }
+    }
+
+    fn output(
+        &self,
+        source: redpitaya_scpi::generator::Source,
+    ) -> &relm4::Controller<output::Model> {
+        match source {
+            redpitaya_scpi::generator::Source::OUT1 => &self.out1,
+            redpitaya_scpi::generator::Source::OUT2 => &self.out2,
+        }
+    }
+
+    fn other(
+        &self,
+        source: redpitaya_scpi::generator::Source,
+    ) -> &relm4::Controller<output::Model> {
+        match source {
+            redpitaya_scpi::generator::Source::OUT1 => &self.out2,
+            redpitaya_scpi::generator::Source::OUT2 => &self.out1,
+        }
+    }
+
+    fn synchronize(&self) {
+        let synchronize = || {
+            if self.link {
+                self.socket.send("OUTPUT:STATE ON")?;
+            }
+
+            self.socket.send("SOUR:TRIG:INT")
+        };
+
+        if let Err(err) = synchronize() {
+            log::warn!("Unable to synchronize outputs: {err}");
+        }
+    }
+
+    pub(super) fn axes(&self, data: &crate::application::Data) -> Vec<crate::application::Axis> {
+        [&self.out1, &self.out2]
+            .iter()
+            .filter_map(|output| output.model().axis(data))
+            .collect()
+    }
+
+    pub(super) fn xy(&self, data: &crate::application::Data) -> Option<(Vec<f64>, Vec<f64>)> {
+        let (out1, out2) = (self.out1.model(), self.out2.model());
+        let times = (0..data.scales.n_samples)
+            .map(|sample| data.scales.sample_to_ms(sample))
+            .collect::<Vec<_>>();
+
+        let (_, x) = out1.samples(&times)?;
+        let (_, y) = out2.samples(&times)?;
+
+        Some((
+            x.iter().map(|x| x / out1.scale()).collect(),
+            y.iter().map(|y| y / out2.scale()).collect(),
+        ))
+    }
+
+    pub(super) fn samples(&self, times: &[f64]) -> Vec<(String, Vec<f64>)> {
+        [&self.out1, &self.out2]
+            .iter()
+            .filter_map(|output| output.model().samples(times))
+            .collect()
+    }
+
+    pub(super) fn draw(
         &self,
-        context: Box<gtk::cairo::Context>,
-        model: Box<crate::application::Data>,
+        context: &gtk::cairo::Context,
+        data: &crate::application::Data,
     ) -> Result<(), gtk::cairo::Error> {
         context.save()?;
-        self.out1
-            .emit(output::InputMsg::Redraw(context.clone(), model.clone()));
+        self.out1.model().draw(context, data)?;
         context.restore()?;
         context.save()?;
-        self.out2
-            .emit(output::InputMsg::Redraw(context.clone(), model));
+        self.out2.model().draw(context, data)?;
         context.restore()
     }
 }
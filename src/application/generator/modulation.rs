@@ -0,0 +1,273 @@
+use gtk::prelude::*;
+use relm4::ComponentController as _;
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Kind {
+    #[default]
+    Off,
+    Am,
+    Fm,
+    Pm,
+    Fsk,
+}
+
+impl Kind {
+    fn depth(self) -> &'static str {
+        match self {
+            Self::Off => "Depth",
+            Self::Am => "Depth (%)",
+            Self::Fm => "Deviation (Hz)",
+            Self::Pm => "Deviation (°)",
+            Self::Fsk => "Hop (Hz)",
+        }
+    }
+}
+
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match *self {
+            Self::Off => "Off",
+            Self::Am => "AM",
+            Self::Fm => "FM",
+            Self::Pm => "PM",
+            Self::Fsk => "FSK",
+        };
+
+        f.write_str(s)
+    }
+}
+
+#[derive(Debug)]
+pub enum InputMsg {
+    Carrier(f64),
+    Depth(f64),
+    Frequency(f64),
+    Kind(Kind),
+    Restore(crate::application::session::Modulation),
+}
+
+#[derive(Debug)]
+pub enum OutputMsg {
+    Change(crate::application::session::Modulation),
+}
+
+pub struct Model {
+    carrier: Result<f64, String>,
+    kind: relm4::Controller<crate::widget::RadioGroup<Kind>>,
+    settings: crate::application::session::Modulation,
+}
+
+#[relm4::component(pub)]
+impl relm4::SimpleComponent for Model {
+    type Init = ();
+    type Input = InputMsg;
+    type Output = OutputMsg;
+
+    fn init(
+        _: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        use relm4::Component as _;
+
+        let kind = crate::widget::RadioGroup::builder()
+            .launch(crate::widget::radio::Options {
+                options: vec![Kind::Off, Kind::Am, Kind::Fm, Kind::Pm, Kind::Fsk],
+                current: Some(Kind::Off),
+                label: "Modulation",
+            })
+            .forward(sender.input_sender(), |output| {
+                let crate::widget::radio::OutputMsg::Change(kind) = output;
+                InputMsg::Kind(kind)
+            });
+
+        let settings = crate::application::session::Modulation::default();
+
+        let model = Self {
+            carrier: carrier(&settings),
+            kind,
+            settings,
+        };
+
+        let widgets = view_output!();
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>) {
+        match msg {
+            InputMsg::Carrier(carrier) => self.settings.carrier = carrier,
+            InputMsg::Depth(depth) => self.settings.depth = depth,
+            InputMsg::Frequency(frequency) => self.settings.frequency = frequency,
+            InputMsg::Kind(kind) => self.settings.kind = kind,
+            InputMsg::Restore(settings) => {
+                self.settings = settings;
+                self.kind
+                    .emit(crate::widget::radio::InputMsg::Set(settings.kind));
+            }
+        }
+
+        self.carrier = carrier(&self.settings);
+
+        if self.carrier.is_ok() || self.settings.kind == Kind::Off {
+            sender.output(OutputMsg::Change(self.settings)).ok();
+        }
+    }
+
+    view! {
+        gtk::Frame {
+            set_label: Some("Modulation"),
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 5,
+
+                append: model.kind.widget(),
+
+                gtk::Grid {
+                    #[watch]
+                    set_sensitive: model.settings.kind != Kind::Off,
+                    set_column_spacing: 5,
+                    set_row_spacing: 5,
+
+                    attach[0, 0, 1, 1] = &gtk::Label {
+                        set_label: "Carrier (Hz)",
+                        set_xalign: 0.0,
+                    },
+                    attach[1, 0, 1, 1] = &gtk::SpinButton::with_range(1.0, 62_500_000.0, 1.0) {
+                        set_hexpand: true,
+                        #[watch]
+                        set_value: model.settings.carrier,
+
+                        connect_value_changed[sender] => move |this| {
+                            sender.input(InputMsg::Carrier(this.value()));
+                        },
+                    },
+                    attach[0, 1, 1, 1] = &gtk::Label {
+                        set_label: "Modulation (Hz)",
+                        set_xalign: 0.0,
+                    },
+                    attach[1, 1, 1, 1] = &gtk::SpinButton::with_range(1.0, 1_000_000.0, 1.0) {
+                        set_hexpand: true,
+                        #[watch]
+                        set_value: model.settings.frequency,
+
+                        connect_value_changed[sender] => move |this| {
+                            sender.input(InputMsg::Frequency(this.value()));
+                        },
+                    },
+                    attach[0, 2, 1, 1] = &gtk::Label {
+                        #[watch]
+                        set_label: model.settings.kind.depth(),
+                        set_xalign: 0.0,
+                    },
+                    attach[1, 2, 1, 1] = &gtk::SpinButton::with_range(0.0, 62_500_000.0, 1.0) {
+                        set_hexpand: true,
+                        #[watch]
+                        set_value: model.settings.depth,
+
+                        connect_value_changed[sender] => move |this| {
+                            sender.input(InputMsg::Depth(this.value()));
+                        },
+                    },
+                    attach[0, 3, 1, 1] = &gtk::Label {
+                        set_label: "Actual carrier (Hz)",
+                        set_xalign: 0.0,
+                    },
+                    attach[1, 3, 1, 1] = &gtk::Label {
+                        set_xalign: 0.0,
+                        #[watch]
+                        set_label: &model
+                            .carrier
+                            .as_ref()
+                            .map(|carrier| format!("{carrier:.0}"))
+                            .unwrap_or_default(),
+                    },
+                },
+                gtk::Label {
+                    add_css_class: "error",
+                    set_wrap: true,
+                    set_xalign: 0.0,
+                    #[watch]
+                    set_label: model.carrier.as_ref().err().map(String::as_str).unwrap_or_default(),
+                    #[watch]
+                    set_visible: model.settings.kind != Kind::Off && model.carrier.is_err(),
+                },
+            },
+        }
+    }
+}
+
+const MAX_CYCLES: f64 = (crate::waveform::LEN / 8) as f64;
+
+pub(super) fn carrier(settings: &crate::application::session::Modulation) -> Result<f64, String> {
+    let frequency = settings.frequency.max(f64::EPSILON);
+    let ratio = settings.carrier / frequency;
+    let cycles = ratio.round().max(1.0);
+    let deviation = settings.depth / frequency;
+
+    let (actual, peak) = match settings.kind {
+        Kind::Off | Kind::Am => (cycles, cycles),
+        Kind::Fm => (cycles, cycles + deviation.abs()),
+        Kind::Pm => (cycles, cycles + settings.depth.to_radians().abs()),
+        Kind::Fsk => {
+            let mark = (ratio / 2.0).round().max(1.0) * 2.0;
+            let space = ((ratio + deviation) / 2.0).round().max(1.0) * 2.0;
+
+            (mark, mark.max(space))
+        }
+    };
+
+    if peak > MAX_CYCLES {
+        return Err(format!(
+            "Carrier too high: at most {:.0} Hz for a {} Hz modulation",
+            settings.frequency * MAX_CYCLES,
+            settings.frequency,
+        ));
+    }
+
+    Ok(actual * settings.frequency)
+}
+
+pub(super) fn waveform(
+    settings: &crate::application::session::Modulation,
+) -> Result<Vec<f64>, String> {
+    use std::f64::consts::TAU;
+
+    carrier(settings)?;
+
+    let ratio = settings.carrier / settings.frequency.max(f64::EPSILON);
+    let cycles = ratio.round().max(1.0);
+    let deviation = settings.depth / settings.frequency.max(f64::EPSILON);
+
+    let waveform = (0..crate::waveform::LEN)
+        .map(|i| {
+            let x = i as f64 / crate::waveform::LEN as f64;
+
+            match settings.kind {
+                Kind::Off => (TAU * cycles * x).sin(),
+                Kind::Am => {
+                    let depth = (settings.depth / 100.0).clamp(0.0, 1.0);
+
+                    (1.0 + depth * (TAU * x).sin()) * (TAU * cycles * x).sin() / (1.0 + depth)
+                }
+                Kind::Fm => (TAU * cycles * x + deviation * (TAU * x).sin()).sin(),
+                Kind::Pm => {
+                    (TAU * cycles * x + settings.depth.to_radians() * (TAU * x).sin()).sin()
+                }
+                Kind::Fsk => {
+                    let mark = (ratio / 2.0).round().max(1.0);
+                    let space = ((ratio + deviation) / 2.0).round().max(1.0);
+
+                    if x < 0.5 {
+                        (TAU * mark * 2.0 * x).sin()
+                    } else {
+                        (TAU * space * 2.0 * (x - 0.5)).sin()
+                    }
+                }
+            }
+        })
+        .collect();
+
+    Ok(waveform)
+}
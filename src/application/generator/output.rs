@@ -4,14 +4,24 @@ use relm4::ComponentController as _;
 
 #[derive(Debug)]
 pub enum InputMsg {
+    Abort,
     Amplitude(f32),
+    Arbitrary(Vec<f64>),
+    Burst(crate::application::session::Burst),
     DutyCycle(f32),
+    Enable(bool),
     Frequency(u32),
     Offset(f32),
+    Phase(i32),
     Form(redpitaya_scpi::generator::Form),
-    Redraw(Box<gtk::cairo::Context>, Box<crate::application::Data>),
+    Modulation(crate::application::session::Modulation),
+    Reapply,
+    Restore(crate::application::session::Output),
+    Scale(f64),
     Start,
     Stop,
+    Sweep(f64, Option<f64>),
+    SweepStop,
 }
 
 #[derive(Debug)]
@@ -20,21 +30,45 @@ pub enum OutputMsg {
     Stop,
 }
 
+#[derive(Clone, Copy, Debug)]
+struct Settings {
+    amplitude: f32,
+    duty_cycle: f32,
+    form: redpitaya_scpi::generator::Form,
+    frequency: u32,
+    offset: f32,
+    phase: i32,
+    started: bool,
+}
+
 pub struct Model {
     amplitude: relm4::Controller<crate::widget::PreciseScale>,
+    arbitrary: relm4::Controller<super::arbitrary::Model>,
+    waveform: Vec<f64>,
+    burst: relm4::Controller<super::burst::Model>,
+    burst_settings: crate::application::session::Burst,
     duty_cycle: relm4::Controller<crate::widget::PreciseScale>,
     form: relm4::Controller<crate::widget::RadioGroup<redpitaya_scpi::generator::Form>>,
     frequency: relm4::Controller<crate::widget::PreciseScale>,
-    generator: redpitaya_scpi::generator::Generator,
+    generator: crate::redpitaya::Generator,
+    modulation: relm4::Controller<super::modulation::Model>,
+    modulation_settings: crate::application::session::Modulation,
     offset: relm4::Controller<crate::widget::PreciseScale>,
     palette: relm4::Controller<crate::widget::Palette>,
+    phase: relm4::Controller<crate::widget::PreciseScale>,
+    scale: f64,
+    scale_select: relm4::Controller<crate::widget::Division>,
+    settings: Settings,
     source: redpitaya_scpi::generator::Source,
+    sweep: relm4::Controller<super::sweep::Model>,
+    swept: Option<(u32, f32)>,
 }
 
 #[relm4::component(pub)]
 impl relm4::SimpleComponent for Model {
     type Init = (
-        redpitaya_scpi::generator::Generator,
+        crate::redpitaya::Generator,
+        crate::redpitaya::Burst,
         redpitaya_scpi::generator::Source,
     );
     type Input = InputMsg;
@@ -47,7 +81,19 @@ impl relm4::SimpleComponent for Model {
     ) -> relm4::ComponentParts<Self> {
         use relm4::Component as _;
 
-        let (generator, source) = init;
+        let (generator, burst, source) = init;
+
+        let settings = Settings {
+            amplitude: generator.amplitude(source).unwrap_or_default(),
+            duty_cycle: generator.duty_cycle(source).unwrap_or_default(),
+            form: generator
+                .form(source)
+                .unwrap_or(redpitaya_scpi::generator::Form::SINE),
+            frequency: generator.frequency(source).unwrap_or_default(),
+            offset: generator.offset(source).unwrap_or_default(),
+            phase: generator.phase(source).unwrap_or_default(),
+            started: generator.is_started(source).unwrap_or_default(),
+        };
 
         let form = crate::widget::RadioGroup::builder()
             .launch(crate::widget::radio::Options {
@@ -59,9 +105,9 @@ impl relm4::SimpleComponent for Model {
                     redpitaya_scpi::generator::Form::SAWD,
                     redpitaya_scpi::generator::Form::PWM,
                     redpitaya_scpi::generator::Form::DC,
-                    // @TODO redpitaya_scpi::generator::Form::ARBITRARY,
+                    redpitaya_scpi::generator::Form::ARBITRARY,
                 ],
-                current: generator.form(source).ok(),
+                current: Some(settings.form),
                 label: "Form",
             })
             .forward(sender.input_sender(), |output| {
@@ -79,7 +125,7 @@ impl relm4::SimpleComponent for Model {
         let amplitude = crate::widget::PreciseScale::builder()
             .launch(crate::widget::precise::Options {
                 label: "Amplitude (V)",
-                value: generator.amplitude(source).unwrap() as f64,
+                value: settings.amplitude as f64,
                 digits: 2,
                 adjustment: gtk::Adjustment::new(0.0, -1.0, 1.0, 0.1, 1.0, 0.0),
             })
@@ -91,7 +137,7 @@ impl relm4::SimpleComponent for Model {
         let offset = crate::widget::PreciseScale::builder()
             .launch(crate::widget::precise::Options {
                 label: "Offest (V)",
-                value: generator.offset(source).unwrap() as f64,
+                value: settings.offset as f64,
                 digits: 2,
                 adjustment: gtk::Adjustment::new(0.0, -1.0, 1.0, 0.1, 1.0, 0.0),
             })
@@ -103,7 +149,7 @@ impl relm4::SimpleComponent for Model {
         let frequency = crate::widget::PreciseScale::builder()
             .launch(crate::widget::precise::Options {
                 label: "Frequency (Hz)",
-                value: generator.frequency(source).unwrap() as f64,
+                value: settings.frequency as f64,
                 digits: 0,
                 adjustment: gtk::Adjustment::new(0.0, 0.0, 62_500_000.0, 1_000.0, 10_000.0, 0.0),
             })
@@ -112,10 +158,22 @@ impl relm4::SimpleComponent for Model {
                 InputMsg::Frequency(frequency as u32)
             });
 
+        let phase = crate::widget::PreciseScale::builder()
+            .launch(crate::widget::precise::Options {
+                label: "Phase (°)",
+                value: settings.phase as f64,
+                digits: 0,
+                adjustment: gtk::Adjustment::new(0.0, -360.0, 360.0, 1.0, 10.0, 0.0),
+            })
+            .forward(sender.input_sender(), |output| {
+                let crate::widget::precise::OutputMsg::Change(phase) = output;
+                InputMsg::Phase(phase as i32)
+            });
+
         let duty_cycle = crate::widget::PreciseScale::builder()
             .launch(crate::widget::precise::Options {
                 label: "Duty cycle (%)",
-                value: generator.duty_cycle(source).unwrap() as f64,
+                value: settings.duty_cycle as f64,
                 digits: 2,
                 adjustment: gtk::Adjustment::new(0.0, 0.0, 1.0, 0.1, 1.0, 0.0),
             })
@@ -124,15 +182,65 @@ impl relm4::SimpleComponent for Model {
                 InputMsg::DutyCycle(duty_cycle as f32)
             });
 
+        let scale_select = crate::widget::Division::builder()
+            .launch(crate::widget::division::Options {
+                label: "Gain",
+                range: crate::scales::V_DIV,
+                current: 1.0,
+            })
+            .forward(sender.input_sender(), |output| {
+                let crate::widget::division::OutputMsg::Change(scale) = output;
+                InputMsg::Scale(scale)
+            });
+
+        let arbitrary = super::arbitrary::Model::builder()
+            .launch(source.into())
+            .forward(sender.input_sender(), |output| {
+                let super::arbitrary::OutputMsg::Change(waveform) = output;
+                InputMsg::Arbitrary(waveform)
+            });
+        arbitrary
+            .widget()
+            .set_visible(settings.form == redpitaya_scpi::generator::Form::ARBITRARY);
+
+        let burst = super::burst::Model::builder()
+            .launch((generator.clone(), burst, source))
+            .forward(sender.input_sender(), |output| {
+                let super::burst::OutputMsg::Change(settings) = output;
+                InputMsg::Burst(settings)
+            });
+
+        let sweep =
+            super::sweep::Model::builder()
+                .launch(())
+                .forward(sender.input_sender(), |output| match output {
+                    super::sweep::OutputMsg::Step(frequency, amplitude) => {
+                        InputMsg::Sweep(frequency, amplitude)
+                    }
+                    super::sweep::OutputMsg::Stop => InputMsg::SweepStop,
+                });
+
         let model = Self {
             amplitude,
+            arbitrary,
+            waveform: Vec::new(),
+            burst,
+            burst_settings: crate::application::session::Burst::default(),
             duty_cycle,
             form,
             frequency,
             generator,
+            modulation,
+            modulation_settings: crate::application::session::Modulation::default(),
             offset,
             palette,
+            phase,
+            scale: 1.0,
+            scale_select,
+            settings,
             source,
+            sweep,
+            swept: None,
         };
 
         let widgets = view_output!();
@@ -144,7 +252,13 @@ impl relm4::SimpleComponent for Model {
         vbox.append(model.amplitude.widget());
         vbox.append(model.offset.widget());
         vbox.append(model.frequency.widget());
+        vbox.append(model.phase.widget());
         vbox.append(model.duty_cycle.widget());
+        vbox.append(model.arbitrary.widget());
+        vbox.append(model.modulation.widget());
+        vbox.append(model.burst.widget());
+        vbox.append(model.sweep.widget());
+        vbox.append(model.scale_select.widget());
 
         relm4::ComponentParts { model, widgets }
     }
@@ -153,23 +267,127 @@ impl relm4::SimpleComponent for Model {
         use InputMsg::*;
 
         match msg {
-            Amplitude(value) => self.generator.set_amplitude(self.source, value),
-            Offset(value) => self.generator.set_offset(self.source, value),
-            Frequency(value) => self.generator.set_frequency(self.source, value),
-            DutyCycle(value) => self.generator.set_duty_cycle(self.source, value),
+            Abort => {
+                self.swept = None;
+                self.sweep.emit(super::sweep::InputMsg::Abort);
+            }
+            Amplitude(value) => {
+                self.settings.amplitude = value;
+                self.generator.set_amplitude(self.source, value).ok();
+            }
+            Arbitrary(waveform) => self.upload(waveform),
+            Burst(settings) => self.burst_settings = settings,
+            Offset(value) => {
+                self.settings.offset = value;
+                self.generator.set_offset(self.source, value).ok();
+            }
+            Frequency(value) => {
+                self.settings.frequency = value;
+                self.generator.set_frequency(self.source, value).ok();
+            }
+            DutyCycle(value) => {
+                self.settings.duty_cycle = value;
+                self.generator.set_duty_cycle(self.source, value).ok();
+            }
+            Enable(enable) => self
+                .palette
+                .emit(crate::widget::palette::InputMsg::Set(enable)),
+            Phase(value) => {
+                self.settings.phase = value;
+                self.generator.set_phase(self.source, value).ok();
+            }
+            Scale(scale) => self.scale = scale,
             Start => {
-                self.generator.start(self.source);
+                self.settings.started = true;
+                self.generator.start(self.source).ok();
                 sender.output(OutputMsg::Start).ok();
             }
             Stop => {
-                self.generator.stop(self.source);
+                self.settings.started = false;
+                self.generator.stop(self.source).ok();
                 sender.output(OutputMsg::Stop).ok();
             }
-            Redraw(ref context, ref model) => self.draw(context, model).unwrap(),
+            Sweep(frequency, amplitude) => {
+                self.swept
+                    .get_or_insert((self.settings.frequency, self.settings.amplitude));
+
+                self.sweep_to(frequency.round() as u32, amplitude.map(|x| x as f32));
+            }
+            SweepStop => {
+                if let Some((frequency, amplitude)) = self.swept.take() {
+                    self.sweep_to(frequency, Some(amplitude));
+                }
+            }
+            Reapply => {
+                self.reapply().ok();
+            }
+            Restore(output) => {
+                if !output.arbitrary.is_empty() {
+                    self.arbitrary
+                        .emit(super::arbitrary::InputMsg::Set(output.arbitrary.clone()));
+                    self.upload(output.arbitrary);
+                }
+
+                self.form
+                    .emit(crate::widget::radio::InputMsg::Set(output.form));
+                self.amplitude.emit(crate::widget::precise::InputMsg::Set(
+                    output.amplitude as f64,
+                ));
+                self.offset
+                    .emit(crate::widget::precise::InputMsg::Set(output.offset as f64));
+                self.frequency.emit(crate::widget::precise::InputMsg::Set(
+                    output.frequency as f64,
+                ));
+                self.phase
+                    .emit(crate::widget::precise::InputMsg::Set(output.phase as f64));
+                self.duty_cycle.emit(crate::widget::precise::InputMsg::Set(
+                    output.duty_cycle as f64,
+                ));
+                self.scale_select
+                    .emit(crate::widget::division::InputMsg::Set(output.scale));
+                self.burst
+                    .emit(super::burst::InputMsg::Restore(output.burst));
+                self.modulation
+                    .emit(super::modulation::InputMsg::Restore(output.modulation));
+                self.sweep
+                    .emit(super::sweep::InputMsg::Restore(output.sweep));
+                self.palette
+                    .emit(crate::widget::palette::InputMsg::Set(output.enable));
+            }
             Form(form) => {
                 let is_pwm = form == redpitaya_scpi::generator::Form::PWM;
                 self.duty_cycle.widget().set_visible(is_pwm);
-                self.generator.set_form(self.source, form);
+                self.arbitrary.widget().set_visible(
+                    form == redpitaya_scpi::generator::Form::ARBITRARY
+                        && self.modulation_settings.kind == super::modulation::Kind::Off,
+                );
+                self.settings.form = form;
+                self.generator.set_form(self.source, form).ok();
+            }
+            Modulation(settings) => {
+                let previous = std::mem::replace(&mut self.modulation_settings, settings);
+
+                if settings.kind != super::modulation::Kind::Off {
+                    match super::modulation::waveform(&settings) {
+                        Ok(waveform) => self.upload(waveform),
+                        Err(err) => {
+                            log::warn!("{err}");
+                            return;
+                        }
+                    }
+                    self.arbitrary.widget().set_visible(false);
+                    self.form.emit(crate::widget::radio::InputMsg::Set(
+                        redpitaya_scpi::generator::Form::ARBITRARY,
+                    ));
+                    self.frequency
+                        .emit(crate::widget::precise::InputMsg::Set(settings.frequency));
+                } else if previous.kind != super::modulation::Kind::Off {
+                    self.form.emit(crate::widget::radio::InputMsg::Set(
+                        redpitaya_scpi::generator::Form::SINE,
+                    ));
+                    self.frequency
+                        .emit(crate::widget::precise::InputMsg::Set(settings.carrier));
+                }
             }
         };
     }
@@ -186,8 +404,84 @@ impl relm4::SimpleComponent for Model {
 }
 
 impl Model {
-    fn is_started(&self) -> bool {
-        self.generator.is_started(self.source)
+    pub(super) fn is_started(&self) -> bool {
+        self.settings.started
+    }
+
+    pub(super) fn session(&self) -> crate::application::session::Output {
+        crate::application::session::Output {
+            enable: self.is_started(),
+            form: self.settings.form,
+            amplitude: self.settings.amplitude,
+            offset: self.settings.offset,
+            frequency: self.settings.frequency,
+            phase: self.settings.phase,
+            duty_cycle: self.settings.duty_cycle,
+            scale: self.scale,
+            arbitrary: self.waveform.clone(),
+            burst: self.burst_settings,
+            modulation: self.modulation_settings,
+            sweep: self.sweep.model().session(),
+        }
+    }
+
+    fn reapply(&self) -> std::io::Result<()> {
+        let settings = self.settings;
+
+        self.generator.set_form(self.source, settings.form)?;
+        self.generator
+            .set_amplitude(self.source, settings.amplitude)?;
+        self.generator.set_offset(self.source, settings.offset)?;
+        self.generator
+            .set_frequency(self.source, settings.frequency)?;
+        self.generator.set_phase(self.source, settings.phase)?;
+        self.generator
+            .set_duty_cycle(self.source, settings.duty_cycle)?;
+
+        if settings.started {
+            self.generator.start(self.source)
+        } else {
+            self.generator.stop(self.source)
+        }
+    }
+
+    fn sweep_to(&mut self, frequency: u32, amplitude: Option<f32>) {
+        self.settings.frequency = frequency;
+        self.generator.set_frequency(self.source, frequency).ok();
+        self.frequency
+            .emit(crate::widget::precise::InputMsg::Display(frequency as f64));
+
+        if let Some(amplitude) = amplitude {
+            self.settings.amplitude = amplitude;
+            self.generator.set_amplitude(self.source, amplitude).ok();
+            self.amplitude
+                .emit(crate::widget::precise::InputMsg::Display(amplitude as f64));
+        }
+    }
+
+    fn upload(&mut self, waveform: Vec<f64>) {
+        let data = waveform
+            .iter()
+            .map(|value| *value as f32)
+            .collect::<Vec<_>>();
+
+        self.generator
+            .set_arbitrary_waveform(self.source, &data)
+            .ok();
+        self.waveform = waveform;
+    }
+
+    pub(super) fn axis(&self, data: &crate::application::Data) -> Option<crate::application::Axis> {
+        if !self.is_started() {
+            return None;
+        }
+
+        Some(crate::application::Axis {
+            name: self.source.to_string(),
+            color: self.source.into(),
+            offset: data.offset(self.source),
+            scale: self.scale,
+        })
     }
 
     fn draw_data(
@@ -197,41 +491,94 @@ impl Model {
     ) -> Result<(), gtk::cairo::Error> {
         context.set_line_width(0.05);
 
-        if let Ok(form) = self.generator.form(self.source) {
-            let amplitude = self.generator.amplitude(self.source).unwrap_or_default();
-            let frequency = self.generator.frequency(self.source).unwrap_or_default() as f32;
-            let duty_cycle = self.generator.duty_cycle(self.source).unwrap_or_default();
-            let offset = self.generator.offset(self.source).unwrap_or_default();
-
-            for sample in (scales.h.0 as i32)..(scales.h.1 as i32) {
-                let x = scales.x_to_offset(sample) as f32;
-                let mut y = match form {
-                    redpitaya_scpi::generator::Form::SINE => self.sine(x, amplitude, frequency),
-                    redpitaya_scpi::generator::Form::SQUARE => self.square(x, amplitude, frequency),
+        let times = (0..=scales.window.width)
+            .map(|pixel| scales.x_to_offset(pixel))
+            .collect::<Vec<_>>();
+
+        for (x, y) in times.iter().zip(self.values(&times)) {
+            let y = y / self.scale;
+
+            context.line_to(*x, y);
+            context.move_to(*x, y);
+        }
+
+        context.stroke()
+    }
+
+    pub(super) fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    pub(super) fn samples(&self, times: &[f64]) -> Option<(String, Vec<f64>)> {
+        if !self.is_started() {
+            return None;
+        }
+
+        Some((self.source.to_string(), self.values(times)))
+    }
+
+    fn values(&self, times: &[f64]) -> Vec<f64> {
+        let Settings {
+            amplitude,
+            duty_cycle,
+            form,
+            offset,
+            ..
+        } = self.settings;
+        let frequency = self.settings.frequency as f32;
+        let phase = self.settings.phase as f32 / 360.0;
+        let shift = if frequency > 0.0 {
+            phase / frequency
+        } else {
+            0.0
+        };
+
+        times
+            .iter()
+            .map(|x| {
+                let Some(t) = self.burst_time(*x as f32 / 1_000_000.0, frequency) else {
+                    return offset.clamp(-1.0, 1.0) as f64;
+                };
+                let t = t + shift;
+                let y = match form {
+                    redpitaya_scpi::generator::Form::SINE => self.sine(t, amplitude, frequency),
+                    redpitaya_scpi::generator::Form::SQUARE => self.square(t, amplitude, frequency),
                     redpitaya_scpi::generator::Form::TRIANGLE => {
-                        self.triangle(x, amplitude, frequency)
+                        self.triangle(t, amplitude, frequency)
                     }
-                    redpitaya_scpi::generator::Form::SAWU => self.sawu(x, amplitude, frequency),
-                    redpitaya_scpi::generator::Form::SAWD => self.sawd(x, amplitude, frequency),
-                    redpitaya_scpi::generator::Form::DC => self.dc(x, amplitude, frequency),
+                    redpitaya_scpi::generator::Form::SAWU => self.sawu(t, amplitude, frequency),
+                    redpitaya_scpi::generator::Form::SAWD => self.sawd(t, amplitude, frequency),
+                    redpitaya_scpi::generator::Form::DC => self.dc(t, amplitude, frequency),
                     redpitaya_scpi::generator::Form::PWM => {
-                        self.pwm(x, amplitude, frequency, duty_cycle)
+                        self.pwm(t, amplitude, frequency, duty_cycle)
+                    }
+                    redpitaya_scpi::generator::Form::ARBITRARY => {
+                        self.arbitrary(t, amplitude, frequency)
                     }
-                    _ => unimplemented!(),
                 };
 
-                y += offset;
+                (y + offset).clamp(-1.0, 1.0) as f64
+            })
+            .collect()
+    }
 
-                y = y.clamp(-1.0, 1.0);
+    fn burst_time(&self, t: f32, frequency: f32) -> Option<f32> {
+        let burst = &self.burst_settings;
 
-                context.line_to(x.into(), y.into());
-                context.move_to(x.into(), y.into());
-            }
+        if !burst.enable {
+            return Some(t);
+        }
 
-            context.stroke()?;
+        let period = burst.period as f32 / 1_000_000.0;
+        let n = (t / period).floor();
+
+        if t < 0.0 || n >= burst.repetitions as f32 {
+            return None;
         }
 
-        Ok(())
+        let t = t - n * period;
+
+        (t * frequency < burst.count as f32).then_some(t)
     }
 
     fn sine(&self, x: f32, amplitude: f32, frequency: f32) -> f32 {
@@ -255,6 +602,21 @@ impl Model {
         amplitude * (1.0 - (x * frequency).fract())
     }
 
+    fn arbitrary(&self, x: f32, amplitude: f32, frequency: f32) -> f32 {
+        if self.waveform.is_empty() {
+            return 0.0;
+        }
+
+        let len = self.waveform.len();
+        let position = (x * frequency).rem_euclid(1.0) * len as f32;
+        let index = position as usize % len;
+        let t = position.fract();
+        let value =
+            self.waveform[index] as f32 * (1.0 - t) + self.waveform[(index + 1) % len] as f32 * t;
+
+        amplitude * value
+    }
+
     fn dc(&self, _: f32, amplitude: f32, _: f32) -> f32 {
         amplitude
     }
@@ -271,7 +633,7 @@ impl Model {
         }
     }
 
-    fn draw(
+    pub(super) fn draw(
         &self,
         context: &gtk::cairo::Context,
         data: &crate::application::Data,
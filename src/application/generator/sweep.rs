@@ -0,0 +1,339 @@
+use gtk::prelude::*;
+use relm4::ComponentController as _;
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Spacing {
+    #[default]
+    Linear,
+    Logarithmic,
+}
+
+impl std::fmt::Display for Spacing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match *self {
+            Self::Linear => "Linear",
+            Self::Logarithmic => "Logarithmic",
+        };
+
+        f.write_str(s)
+    }
+}
+
+#[derive(Debug)]
+pub enum Command {
+    Done,
+    Step(usize),
+}
+
+#[derive(Debug)]
+pub enum InputMsg {
+    Abort,
+    Amplitude(bool),
+    AmplitudeStart(f64),
+    AmplitudeStop(f64),
+    Dwell(u32),
+    Points(usize),
+    Restore(crate::application::session::Sweep),
+    Spacing(Spacing),
+    Start(f64),
+    Stop(f64),
+    Toggle,
+}
+
+#[derive(Debug)]
+pub enum OutputMsg {
+    Step(f64, Option<f64>),
+    Stop,
+}
+
+pub struct Model {
+    progress: f64,
+    running: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+    settings: crate::application::session::Sweep,
+    spacing: relm4::Controller<crate::widget::RadioGroup<Spacing>>,
+    status: String,
+}
+
+#[relm4::component(pub)]
+impl relm4::Component for Model {
+    type CommandOutput = Command;
+    type Init = ();
+    type Input = InputMsg;
+    type Output = OutputMsg;
+
+    fn init(
+        _: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        use relm4::Component as _;
+
+        let settings = crate::application::session::Sweep::default();
+
+        let spacing = crate::widget::RadioGroup::builder()
+            .launch(crate::widget::radio::Options {
+                options: vec![Spacing::Linear, Spacing::Logarithmic],
+                current: Some(settings.spacing),
+                label: "Spacing",
+            })
+            .forward(sender.input_sender(), |output| {
+                let crate::widget::radio::OutputMsg::Change(spacing) = output;
+                InputMsg::Spacing(spacing)
+            });
+
+        let model = Self {
+            progress: 0.0,
+            running: None,
+            settings,
+            spacing,
+            status: String::new(),
+        };
+
+        let widgets = view_output!();
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>, _: &Self::Root) {
+        match msg {
+            InputMsg::Abort => {
+                if let Some(running) = self.running.take() {
+                    running.store(false, std::sync::atomic::Ordering::Relaxed);
+                    self.status = "Connection lost".to_string();
+                }
+            }
+            InputMsg::Amplitude(enable) => self.settings.amplitude = enable,
+            InputMsg::AmplitudeStart(amplitude) => self.settings.amplitude_start = amplitude,
+            InputMsg::AmplitudeStop(amplitude) => self.settings.amplitude_stop = amplitude,
+            InputMsg::Dwell(dwell) => self.settings.dwell = dwell,
+            InputMsg::Points(points) => self.settings.points = points.max(2),
+            InputMsg::Restore(settings) => {
+                self.settings = settings;
+                self.spacing
+                    .emit(crate::widget::radio::InputMsg::Set(settings.spacing));
+            }
+            InputMsg::Spacing(spacing) => self.settings.spacing = spacing,
+            InputMsg::Start(frequency) => self.settings.start = frequency,
+            InputMsg::Stop(frequency) => self.settings.stop = frequency,
+            InputMsg::Toggle => match self.running.take() {
+                Some(running) => {
+                    running.store(false, std::sync::atomic::Ordering::Relaxed);
+                    self.status = "Stopped".to_string();
+                    sender.output(OutputMsg::Stop).ok();
+                }
+                None => self.start(&sender),
+            },
+        }
+    }
+
+    fn update_cmd(
+        &mut self,
+        msg: Self::CommandOutput,
+        sender: relm4::ComponentSender<Self>,
+        _: &Self::Root,
+    ) {
+        match msg {
+            Command::Done => {
+                self.running = None;
+                self.status = "Done".to_string();
+                sender.output(OutputMsg::Stop).ok();
+            }
+            Command::Step(step) => {
+                if self.running.is_none() {
+                    return;
+                }
+
+                let (frequency, amplitude) = self.point(step);
+
+                self.progress = (step + 1) as f64 / self.settings.points as f64;
+                self.status = format!(
+                    "{}/{} - {}",
+                    step + 1,
+                    self.settings.points,
+                    crate::scales::format(frequency, "Hz"),
+                );
+
+                sender.output(OutputMsg::Step(frequency, amplitude)).ok();
+            }
+        }
+    }
+
+    view! {
+        gtk::Frame {
+            set_label: Some("Sweep"),
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 5,
+
+                gtk::Grid {
+                    #[watch]
+                    set_sensitive: model.running.is_none(),
+                    set_column_spacing: 5,
+                    set_row_spacing: 5,
+
+                    attach[0, 0, 1, 1] = &gtk::Label {
+                        set_label: "Start (Hz)",
+                        set_xalign: 0.0,
+                    },
+                    attach[1, 0, 1, 1] = &gtk::SpinButton::with_range(1.0, 62_500_000.0, 1.0) {
+                        set_hexpand: true,
+                        #[watch]
+                        set_value: model.settings.start,
+
+                        connect_value_changed[sender] => move |this| {
+                            sender.input(InputMsg::Start(this.value()));
+                        },
+                    },
+                    attach[0, 1, 1, 1] = &gtk::Label {
+                        set_label: "Stop (Hz)",
+                        set_xalign: 0.0,
+                    },
+                    attach[1, 1, 1, 1] = &gtk::SpinButton::with_range(1.0, 62_500_000.0, 1.0) {
+                        set_hexpand: true,
+                        #[watch]
+                        set_value: model.settings.stop,
+
+                        connect_value_changed[sender] => move |this| {
+                            sender.input(InputMsg::Stop(this.value()));
+                        },
+                    },
+                    attach[0, 2, 1, 1] = &gtk::Label {
+                        set_label: "Points",
+                        set_xalign: 0.0,
+                    },
+                    attach[1, 2, 1, 1] = &gtk::SpinButton::with_range(2.0, 10_000.0, 1.0) {
+                        set_hexpand: true,
+                        #[watch]
+                        set_value: model.settings.points as f64,
+
+                        connect_value_changed[sender] => move |this| {
+                            sender.input(InputMsg::Points(this.value() as usize));
+                        },
+                    },
+                    attach[0, 3, 1, 1] = &gtk::Label {
+                        set_label: "Dwell (ms)",
+                        set_xalign: 0.0,
+                    },
+                    attach[1, 3, 1, 1] = &gtk::SpinButton::with_range(10.0, 60_000.0, 10.0) {
+                        set_hexpand: true,
+                        #[watch]
+                        set_value: model.settings.dwell as f64,
+
+                        connect_value_changed[sender] => move |this| {
+                            sender.input(InputMsg::Dwell(this.value() as u32));
+                        },
+                    },
+                    attach[0, 4, 2, 1] = model.spacing.widget(),
+                    attach[0, 5, 2, 1] = &gtk::CheckButton {
+                        set_label: Some("Amplitude ramp"),
+                        #[watch]
+                        set_active: model.settings.amplitude,
+
+                        connect_toggled[sender] => move |this| {
+                            sender.input(InputMsg::Amplitude(this.is_active()));
+                        },
+                    },
+                    attach[0, 6, 1, 1] = &gtk::Label {
+                        set_label: "From (V)",
+                        set_xalign: 0.0,
+                    },
+                    attach[1, 6, 1, 1] = &gtk::SpinButton::with_range(0.0, 1.0, 0.01) {
+                        set_digits: 2,
+                        set_hexpand: true,
+                        #[watch]
+                        set_sensitive: model.settings.amplitude,
+                        #[watch]
+                        set_value: model.settings.amplitude_start,
+
+                        connect_value_changed[sender] => move |this| {
+                            sender.input(InputMsg::AmplitudeStart(this.value()));
+                        },
+                    },
+                    attach[0, 7, 1, 1] = &gtk::Label {
+                        set_label: "To (V)",
+                        set_xalign: 0.0,
+                    },
+                    attach[1, 7, 1, 1] = &gtk::SpinButton::with_range(0.0, 1.0, 0.01) {
+                        set_digits: 2,
+                        set_hexpand: true,
+                        #[watch]
+                        set_sensitive: model.settings.amplitude,
+                        #[watch]
+                        set_value: model.settings.amplitude_stop,
+
+                        connect_value_changed[sender] => move |this| {
+                            sender.input(InputMsg::AmplitudeStop(this.value()));
+                        },
+                    },
+                },
+                gtk::ProgressBar {
+                    set_show_text: true,
+                    #[watch]
+                    set_fraction: model.progress,
+                    #[watch]
+                    set_text: Some(&model.status),
+                },
+                gtk::Button {
+                    #[watch]
+                    set_label: if model.running.is_some() { "Stop sweep" } else { "Start sweep" },
+
+                    connect_clicked[sender] => move |_| {
+                        sender.input(InputMsg::Toggle);
+                    },
+                },
+            },
+        }
+    }
+}
+
+impl Model {
+    pub(super) fn session(&self) -> crate::application::session::Sweep {
+        self.settings
+    }
+
+    fn point(&self, step: usize) -> (f64, Option<f64>) {
+        let settings = &self.settings;
+        let ratio = step as f64 / (settings.points - 1) as f64;
+
+        let frequency = match settings.spacing {
+            Spacing::Linear => settings.start + (settings.stop - settings.start) * ratio,
+            Spacing::Logarithmic => settings.start * (settings.stop / settings.start).powf(ratio),
+        };
+        let amplitude = settings.amplitude.then(|| {
+            settings.amplitude_start + (settings.amplitude_stop - settings.amplitude_start) * ratio
+        });
+
+        (frequency, amplitude)
+    }
+
+    fn start(&mut self, sender: &relm4::ComponentSender<Self>) {
+        let running = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let points = self.settings.points;
+        let dwell = std::time::Duration::from_millis(self.settings.dwell as u64);
+
+        self.running = Some(running.clone());
+        self.progress = 0.0;
+
+        sender.command(move |out, shutdown| {
+            let fut = shutdown
+                .register(async move {
+                    for step in 0..points {
+                        if !running.load(std::sync::atomic::Ordering::Relaxed) {
+                            return;
+                        }
+
+                        out.send(Command::Step(step)).ok();
+                        tokio::time::sleep(dwell).await;
+                    }
+
+                    if running.load(std::sync::atomic::Ordering::Relaxed) {
+                        out.send(Command::Done).ok();
+                    }
+                })
+                .drop_on_shutdown();
+
+            Box::pin(fut)
+        });
+    }
+}
@@ -9,6 +9,7 @@ pub enum InputMsg {
     Draw,
     Move(Option<(f64, f64)>, f64, f64),
     Release,
+    SetLevel(String, i32),
     SourceStart(String),
     SourceStop(String),
 }
@@ -69,6 +70,7 @@ impl relm4::SimpleComponent for Model {
                 self.on_mouse_move(start_point, x as i32, y as i32).unwrap()
             }
             InputMsg::Draw => self.draw().unwrap(),
+            InputMsg::SetLevel(source, offset) => self.set_level(source, offset).unwrap(),
             InputMsg::SourceStart(source) => self.start(source).unwrap(),
             InputMsg::SourceStop(source) => self.stop(source).unwrap(),
             InputMsg::Release => self.on_release(&sender),
@@ -116,12 +118,14 @@ impl Model {
     }
 
     fn set_level(&mut self, name: String, offset: i32) -> Result<(), gtk::cairo::Error> {
-        if let Some(level) = self.levels.get_mut(&name) {
-            level.offset = offset;
-            self.draw()?;
-        }
+        let level = self.levels.entry(name).or_insert(Level {
+            enable: false,
+            offset,
+        });
 
-        Ok(())
+        level.offset = offset;
+
+        self.draw()
     }
 
     fn draw(&mut self) -> Result<(), gtk::cairo::Error> {
@@ -4,21 +4,79 @@ use crate::color::Colorable as _;
 use gtk::prelude::*;
 use relm4::ComponentController as _;
 
+#[derive(Clone, Copy, Debug)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Mode {
+    #[default]
+    Time,
+    Spectrum,
+    Xy,
+}
+
+impl Mode {
+    const ALL: [Self; 3] = [Self::Time, Self::Spectrum, Self::Xy];
+}
+
+impl std::fmt::Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let display = match self {
+            Self::Time => "Time",
+            Self::Spectrum => "Spectrum",
+            Self::Xy => "XY",
+        };
+
+        write!(f, "{display}")
+    }
+}
+
 #[derive(Debug)]
 pub enum InputMsg {
-    Redraw(Box<gtk::cairo::Context>, Box<crate::application::Data>),
+    ClearPersistence,
+    Mode(u32),
+    Motion(f64, f64),
+    PanBegin,
+    PanUpdate(f64, f64),
+    Persistence(u32),
+    Scaling(u32),
+    Scroll(f64, bool),
     SetImage(gtk::cairo::ImageSurface),
+    SetLevel(level::Orientation, String, i32),
     SourceStart(level::Orientation, String),
     SourceStop(level::Orientation, String),
+    Window(u32),
+    XyOutputs(bool),
+    ZoomBegin(f64, f64),
+    ZoomEnd,
+    ZoomUpdate(f64, f64),
 }
 
 #[derive(Debug)]
 pub enum OutputMsg {
+    ClearPersistence,
     Level(String, i32),
+    Pan(i32, i32),
+    Persistence(crate::persistence::Persistence),
+    Redraw,
     Resize(i32, i32),
+    Zoom(Axis, i32, i32, f64),
+    ZoomRect((i32, i32), (i32, i32)),
 }
 
 pub struct Model {
+    band: Option<((f64, f64), (f64, f64))>,
+    image: Option<gtk::cairo::ImageSurface>,
+    mode: Mode,
+    pan: (f64, f64),
+    persistence: crate::persistence::Persistence,
+    pointer: (f64, f64),
+    scaling: crate::spectrum::Scaling,
+    window: crate::spectrum::Window,
+    xy_outputs: bool,
     level_left: relm4::Controller<level::Model>,
     level_top: relm4::Controller<level::Model>,
     level_right: relm4::Controller<level::Model>,
@@ -66,7 +124,26 @@ impl relm4::SimpleComponent for Model {
 
         let p2 = level::placeholder::Model::builder().launch(()).detach();
 
+        let modes = Mode::ALL.map(|mode| mode.to_string());
+        let modes = modes.each_ref().map(String::as_str);
+        let windows = crate::spectrum::Window::ALL.map(|window| window.to_string());
+        let windows = windows.each_ref().map(String::as_str);
+        let scalings = crate::spectrum::Scaling::ALL.map(|scaling| scaling.to_string());
+        let scalings = scalings.each_ref().map(String::as_str);
+        let persistences =
+            crate::persistence::Persistence::ALL.map(|persistence| persistence.to_string());
+        let persistences = persistences.each_ref().map(String::as_str);
+
         let model = Self {
+            band: None,
+            image: None,
+            mode: Mode::default(),
+            pan: (0.0, 0.0),
+            persistence: crate::persistence::Persistence::default(),
+            pointer: (0.0, 0.0),
+            scaling: crate::spectrum::Scaling::default(),
+            window: crate::spectrum::Window::default(),
+            xy_outputs: false,
             level_left,
             level_top,
             level_right,
@@ -81,8 +158,81 @@ impl relm4::SimpleComponent for Model {
         relm4::ComponentParts { model, widgets }
     }
 
-    fn update(&mut self, msg: Self::Input, _: relm4::ComponentSender<Self>) {
+    fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>) {
         match msg {
+            InputMsg::ClearPersistence => {
+                sender.output(OutputMsg::ClearPersistence).ok();
+            }
+            InputMsg::Mode(mode) => {
+                self.mode = Mode::ALL[mode as usize % Mode::ALL.len()];
+                sender.output(OutputMsg::Redraw).ok();
+            }
+            InputMsg::Persistence(persistence) => {
+                self.persistence = crate::persistence::Persistence::ALL
+                    [persistence as usize % crate::persistence::Persistence::ALL.len()];
+                sender.output(OutputMsg::Persistence(self.persistence)).ok();
+            }
+            InputMsg::Scaling(scaling) => {
+                self.scaling = crate::spectrum::Scaling::ALL
+                    [scaling as usize % crate::spectrum::Scaling::ALL.len()];
+                sender.output(OutputMsg::Redraw).ok();
+            }
+            InputMsg::Window(window) => {
+                self.window = crate::spectrum::Window::ALL
+                    [window as usize % crate::spectrum::Window::ALL.len()];
+                sender.output(OutputMsg::Redraw).ok();
+            }
+            InputMsg::XyOutputs(enable) => {
+                self.xy_outputs = enable;
+                sender.output(OutputMsg::Redraw).ok();
+            }
+            InputMsg::Motion(x, y) => self.pointer = (x, y),
+            InputMsg::Scroll(_, _) | InputMsg::PanUpdate(_, _) | InputMsg::ZoomBegin(_, _)
+                if self.mode == Mode::Spectrum => {}
+            InputMsg::Scroll(delta, vertical) => {
+                let axis = if vertical {
+                    Axis::Vertical
+                } else {
+                    Axis::Horizontal
+                };
+
+                sender
+                    .output(OutputMsg::Zoom(
+                        axis,
+                        self.pointer.0 as i32,
+                        self.pointer.1 as i32,
+                        delta,
+                    ))
+                    .ok();
+            }
+            InputMsg::PanBegin => self.pan = (0.0, 0.0),
+            InputMsg::PanUpdate(dx, dy) => {
+                let (x, y) = (dx - self.pan.0, dy - self.pan.1);
+
+                if x as i32 != 0 || y as i32 != 0 {
+                    self.pan.0 += (x as i32) as f64;
+                    self.pan.1 += (y as i32) as f64;
+                    sender.output(OutputMsg::Pan(x as i32, y as i32)).ok();
+                }
+            }
+            InputMsg::ZoomBegin(x, y) => self.band = Some(((x, y), (x, y))),
+            InputMsg::ZoomUpdate(dx, dy) => {
+                if let Some((start, _)) = self.band {
+                    self.band = Some((start, (start.0 + dx, start.1 + dy)));
+                    self.repaint().unwrap();
+                }
+            }
+            InputMsg::ZoomEnd => {
+                if let Some((start, end)) = self.band.take() {
+                    self.repaint().unwrap();
+                    sender
+                        .output(OutputMsg::ZoomRect(
+                            (start.0 as i32, start.1 as i32),
+                            (end.0 as i32, end.1 as i32),
+                        ))
+                        .ok();
+                }
+            }
             InputMsg::SourceStart(orientation, source) => match orientation {
                 level::Orientation::Left => {
                     self.level_left.emit(level::InputMsg::SourceStart(source))
@@ -103,7 +253,9 @@ impl relm4::SimpleComponent for Model {
                 }
                 level::Orientation::Top => self.level_top.emit(level::InputMsg::SourceStop(source)),
             },
-            InputMsg::Redraw(ref context, ref model) => self.draw(context, model).unwrap(),
+            InputMsg::SetLevel(orientation, source, offset) => self
+                .level(orientation)
+                .emit(level::InputMsg::SetLevel(source, offset)),
             InputMsg::SetImage(image) => self.set_image(&image).unwrap(),
         }
     }
@@ -138,6 +290,101 @@ impl relm4::SimpleComponent for Model {
                     connect_resize[sender] => move |_, width, height| {
                         sender.output(OutputMsg::Resize(width, height)).ok();
                     },
+
+                    add_controller = gtk::EventControllerMotion {
+                        connect_motion[sender] => move |_, x, y| {
+                            sender.input(InputMsg::Motion(x, y));
+                        },
+                    },
+                    add_controller = gtk::EventControllerScroll::new(gtk::EventControllerScrollFlags::VERTICAL) {
+                        connect_scroll[sender] => move |controller, _, dy| {
+                            let vertical = controller
+                                .current_event_state()
+                                .contains(gtk::gdk::ModifierType::CONTROL_MASK);
+
+                            sender.input(InputMsg::Scroll(dy, vertical));
+                            gtk::glib::Propagation::Stop
+                        },
+                    },
+                    add_controller = gtk::GestureDrag {
+                        set_button: gtk::gdk::BUTTON_PRIMARY,
+
+                        connect_drag_begin[sender] => move |_, _, _| {
+                            sender.input(InputMsg::PanBegin);
+                        },
+                        connect_drag_update[sender] => move |_, dx, dy| {
+                            sender.input(InputMsg::PanUpdate(dx, dy));
+                        },
+                    },
+                    add_controller = gtk::GestureDrag {
+                        set_button: gtk::gdk::BUTTON_SECONDARY,
+
+                        connect_drag_begin[sender] => move |_, x, y| {
+                            sender.input(InputMsg::ZoomBegin(x, y));
+                        },
+                        connect_drag_update[sender] => move |_, dx, dy| {
+                            sender.input(InputMsg::ZoomUpdate(dx, dy));
+                        },
+                        connect_drag_end[sender] => move |_, _, _| {
+                            sender.input(InputMsg::ZoomEnd);
+                        },
+                    },
+                },
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Horizontal,
+                    set_spacing: 5,
+
+                    gtk::DropDown::from_strings(&modes) {
+                        connect_selected_notify[sender] => move |this| {
+                            sender.input(InputMsg::Mode(this.selected()));
+                        },
+                    },
+                    gtk::DropDown::from_strings(&persistences) {
+                        #[watch]
+                        set_visible: model.mode == Mode::Time,
+                        set_tooltip_text: Some("Persistence"),
+
+                        connect_selected_notify[sender] => move |this| {
+                            sender.input(InputMsg::Persistence(this.selected()));
+                        },
+                    },
+                    gtk::Button {
+                        #[watch]
+                        set_visible: model.mode == Mode::Time
+                            && model.persistence != crate::persistence::Persistence::Off,
+                        set_label: "Clear",
+
+                        connect_clicked[sender] => move |_| {
+                            sender.input(InputMsg::ClearPersistence);
+                        },
+                    },
+                    gtk::DropDown::from_strings(&windows) {
+                        #[watch]
+                        set_visible: model.mode == Mode::Spectrum,
+                        set_tooltip_text: Some("Window"),
+
+                        connect_selected_notify[sender] => move |this| {
+                            sender.input(InputMsg::Window(this.selected()));
+                        },
+                    },
+                    gtk::DropDown::from_strings(&scalings) {
+                        #[watch]
+                        set_visible: model.mode == Mode::Spectrum,
+                        set_tooltip_text: Some("Scaling"),
+
+                        connect_selected_notify[sender] => move |this| {
+                            sender.input(InputMsg::Scaling(this.selected()));
+                        },
+                    },
+                    gtk::CheckButton {
+                        #[watch]
+                        set_visible: model.mode == Mode::Xy,
+                        set_label: Some("OUT 1 / OUT 2"),
+
+                        connect_toggled[sender] => move |this| {
+                            sender.input(InputMsg::XyOutputs(this.is_active()));
+                        },
+                    },
                 },
             },
             gtk::Box {
@@ -152,7 +399,15 @@ impl relm4::SimpleComponent for Model {
 }
 
 impl Model {
-    fn draw(
+    fn level(&self, orientation: level::Orientation) -> &relm4::Controller<level::Model> {
+        match orientation {
+            level::Orientation::Left => &self.level_left,
+            level::Orientation::Right => &self.level_right,
+            level::Orientation::Top => &self.level_top,
+        }
+    }
+
+    pub(super) fn draw(
         &self,
         context: &gtk::cairo::Context,
         data: &crate::application::Data,
@@ -194,10 +449,217 @@ impl Model {
         Ok(())
     }
 
+    pub(super) fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub(super) fn persistence(&self) -> crate::persistence::Persistence {
+        self.persistence
+    }
+
+    pub(super) fn xy_outputs(&self) -> bool {
+        self.xy_outputs
+    }
+
+    pub(super) fn draw_xy(
+        &self,
+        context: &gtk::cairo::Context,
+        data: &crate::application::Data,
+        curves: Vec<(crate::Color, Vec<f64>, Vec<f64>)>,
+    ) -> Result<(), gtk::cairo::Error> {
+        self.draw(context, data)?;
+
+        context.set_line_width(data.scales.height() / 500.0);
+
+        for (color, x, y) in curves {
+            context.set_color(color);
+            context.new_path();
+
+            for (x, y) in x.iter().zip(&y) {
+                context.line_to(*x, *y);
+            }
+
+            context.stroke()?;
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn draw_spectrum(
+        &self,
+        context: &gtk::cairo::Context,
+        width: f64,
+        height: f64,
+        signals: &[(String, Vec<f64>)],
+        period: f64,
+    ) -> Result<(), gtk::cairo::Error> {
+        const PEAKS: usize = 5;
+
+        let spectra = signals
+            .iter()
+            .map(|(name, samples)| {
+                (
+                    name,
+                    crate::spectrum::Spectrum::new(samples, period, self.window),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let nyquist = 0.5 / period;
+        let (bottom, top) = match self.scaling {
+            crate::spectrum::Scaling::DbV => (-90.0, 10.0),
+            crate::spectrum::Scaling::Linear => {
+                let max = spectra
+                    .iter()
+                    .flat_map(|(_, spectrum)| spectrum.amplitudes.iter().copied())
+                    .fold(0.0, f64::max);
+                let div = crate::scales::steps(crate::scales::V_DIV)
+                    .into_iter()
+                    .find(|div| div * 10.0 >= max)
+                    .unwrap_or(crate::scales::V_DIV.1);
+
+                (0.0, div * 10.0)
+            }
+        };
+        let unit = match self.scaling {
+            crate::spectrum::Scaling::DbV => "dBV",
+            crate::spectrum::Scaling::Linear => "V",
+        };
+        let value = |amplitude: f64| match self.scaling {
+            crate::spectrum::Scaling::DbV => crate::spectrum::db(amplitude),
+            crate::spectrum::Scaling::Linear => amplitude,
+        };
+        let to_x = |frequency: f64| frequency / nyquist * width;
+        let to_y = |value: f64| (top - value) / (top - bottom) * height;
+
+        context.set_color(crate::color::BACKGROUND);
+        context.rectangle(0.0, 0.0, width, height);
+        context.fill()?;
+
+        let font_size = (height / 60.0).max(8.0);
+        context.select_font_face(
+            "monospace",
+            gtk::cairo::FontSlant::Normal,
+            gtk::cairo::FontWeight::Normal,
+        );
+        context.set_font_size(font_size);
+        context.set_line_width(1.0);
+
+        for i in 0..11 {
+            if i % 5 == 0 {
+                context.set_color(crate::color::MAIN_SCALE);
+            } else {
+                context.set_color(crate::color::SECONDARY_SCALE);
+            }
+
+            let x = width / 10.0 * i as f64;
+            context.move_to(x, 0.0);
+            context.line_to(x, height);
+            context.stroke()?;
+
+            let y = height / 10.0 * i as f64;
+            context.move_to(0.0, y);
+            context.line_to(width, y);
+            context.stroke()?;
+
+            context.set_color(crate::color::MAIN_SCALE);
+
+            let frequency = crate::scales::format(nyquist / 10.0 * i as f64, "Hz");
+            let extents = context.text_extents(&frequency)?;
+            context.move_to(
+                (x - extents.x_advance() / 2.0).clamp(0.0, width - extents.x_advance()),
+                height - font_size / 2.0,
+            );
+            context.show_text(&frequency)?;
+
+            let level = top - (top - bottom) / 10.0 * i as f64;
+            let level = match self.scaling {
+                crate::spectrum::Scaling::DbV => format!("{level} {unit}"),
+                crate::spectrum::Scaling::Linear => crate::scales::format(level, unit),
+            };
+            context.move_to(
+                font_size / 2.0,
+                (y + font_size / 3.0).clamp(font_size, height),
+            );
+            context.show_text(&level)?;
+        }
+
+        let mut line = 1.0;
+
+        for (name, spectrum) in &spectra {
+            context.set_color((*name).clone().into());
+            context.new_path();
+
+            for (bin, amplitude) in spectrum.amplitudes.iter().enumerate() {
+                context.line_to(
+                    to_x(spectrum.frequency(bin)),
+                    to_y(value(*amplitude)).clamp(0.0, height),
+                );
+            }
+            context.stroke()?;
+
+            let mut peaks = Vec::new();
+
+            for bin in spectrum.peaks(PEAKS) {
+                let x = to_x(spectrum.frequency(bin));
+                let y = to_y(value(spectrum.amplitudes[bin])).clamp(0.0, height);
+
+                context.move_to(x, y - 2.0);
+                context.line_to(x - 5.0, y - 10.0);
+                context.line_to(x + 5.0, y - 10.0);
+                context.close_path();
+                context.fill()?;
+
+                let amplitude = match self.scaling {
+                    crate::spectrum::Scaling::DbV => {
+                        format!("{:.1} dBV", value(spectrum.amplitudes[bin]))
+                    }
+                    crate::spectrum::Scaling::Linear => {
+                        crate::scales::format(spectrum.amplitudes[bin], "V")
+                    }
+                };
+
+                peaks.push(format!(
+                    "{} {amplitude}",
+                    crate::scales::format(spectrum.frequency(bin), "Hz")
+                ));
+            }
+
+            let text = format!("{name}: {}", peaks.join(", "));
+            let extents = context.text_extents(&text)?;
+
+            context.move_to(
+                width - extents.x_advance() - font_size,
+                line * font_size * 1.5,
+            );
+            context.show_text(&text)?;
+            line += 1.0;
+        }
+
+        Ok(())
+    }
+
     fn set_image(&mut self, image: &gtk::cairo::ImageSurface) -> Result<(), gtk::cairo::Error> {
+        self.image = Some(image.clone());
+        self.repaint()
+    }
+
+    fn repaint(&mut self) -> Result<(), gtk::cairo::Error> {
         let context = self.handler.get_context();
 
-        context.set_source_surface(image, 0., 0.)?;
-        context.paint()
+        if let Some(image) = &self.image {
+            context.set_source_surface(image, 0., 0.)?;
+            context.paint()?;
+        }
+
+        if let Some(((x0, y0), (x1, y1))) = self.band {
+            context.set_color(crate::color::MAIN_SCALE);
+            context.set_line_width(1.0);
+            context.set_dash(&[4.0, 4.0], 0.0);
+            context.rectangle(x0.min(x1), y0.min(y1), (x1 - x0).abs(), (y1 - y0).abs());
+            context.stroke()?;
+        }
+
+        Ok(())
     }
 }
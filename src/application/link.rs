@@ -0,0 +1,208 @@
+const PROBE: std::time::Duration = std::time::Duration::from_secs(2);
+const BACKOFF: (std::time::Duration, std::time::Duration) = (
+    std::time::Duration::from_secs(1),
+    std::time::Duration::from_secs(30),
+);
+
+#[derive(Debug)]
+pub enum Command {
+    Acquired(Vec<f64>, Vec<f64>),
+    Connection(Result<String, String>),
+    Invalid(String),
+    Lost(String),
+}
+
+#[derive(Debug)]
+pub enum Event {
+    Acquired(Vec<f64>, Vec<f64>),
+    Connected,
+    Disconnected,
+}
+
+pub struct Link {
+    acquiring: bool,
+    addr: String,
+    backoff: std::time::Duration,
+    connected: bool,
+    redpitaya: crate::redpitaya::Redpitaya,
+}
+
+impl Link {
+    pub fn new<C>(
+        redpitaya: crate::redpitaya::Redpitaya,
+        addr: String,
+        sender: &relm4::ComponentSender<C>,
+    ) -> Self
+    where
+        C: relm4::Component,
+        C::CommandOutput: From<Command>,
+    {
+        redpitaya
+            .data
+            .set_units(redpitaya_scpi::data::Unit::VOLTS)
+            .ok();
+        redpitaya.acquire.start().ok();
+
+        let link = Self {
+            acquiring: false,
+            addr,
+            backoff: BACKOFF.0,
+            connected: true,
+            redpitaya,
+        };
+
+        link.probe(sender, PROBE);
+
+        link
+    }
+
+    pub fn addr(&self) -> &str {
+        &self.addr
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    pub fn label(&self) -> String {
+        if self.connected {
+            format!("● {}", self.addr)
+        } else {
+            format!("○ {} disconnected, reconnecting…", self.addr)
+        }
+    }
+
+    pub fn read<C>(&mut self, sender: &relm4::ComponentSender<C>, oldest: bool)
+    where
+        C: relm4::Component,
+        C::CommandOutput: From<Command>,
+    {
+        if self.acquiring || !self.connected {
+            return;
+        }
+
+        self.acquiring = true;
+
+        let data = self.redpitaya.data.clone();
+
+        sender.spawn_oneshot_command(move || {
+            let read = |source| {
+                if oldest {
+                    data.read_oldest(source, 16_384)
+                } else {
+                    data.read_all(source)
+                }
+            };
+
+            let acquisition = read(redpitaya_scpi::acquire::Source::IN1)
+                .and_then(|in1| Ok((in1, read(redpitaya_scpi::acquire::Source::IN2)?)));
+
+            match acquisition {
+                Ok((in1, in2)) => Command::Acquired(in1, in2),
+                Err(err) if err.kind() == std::io::ErrorKind::InvalidData => {
+                    Command::Invalid(err.to_string())
+                }
+                Err(err) => Command::Lost(err.to_string()),
+            }
+            .into()
+        });
+    }
+
+    pub fn update<C>(
+        &mut self,
+        command: Command,
+        sender: &relm4::ComponentSender<C>,
+    ) -> Option<Event>
+    where
+        C: relm4::Component,
+        C::CommandOutput: From<Command>,
+    {
+        match command {
+            Command::Acquired(in1, in2) => {
+                self.acquiring = false;
+
+                Some(Event::Acquired(in1, in2))
+            }
+            Command::Connection(Ok(identity)) => {
+                self.probe(sender, PROBE);
+
+                if self.connected {
+                    return None;
+                }
+
+                log::info!("Reconnected to {identity} at {}", self.addr);
+
+                self.connected = true;
+                self.acquiring = false;
+                self.backoff = BACKOFF.0;
+                self.redpitaya
+                    .data
+                    .set_units(redpitaya_scpi::data::Unit::VOLTS)
+                    .ok();
+                self.redpitaya.acquire.start().ok();
+
+                Some(Event::Connected)
+            }
+            Command::Connection(Err(err)) => {
+                self.probe(sender, self.backoff);
+                self.backoff = (self.backoff * 2).min(BACKOFF.1);
+
+                if !self.connected {
+                    return None;
+                }
+
+                log::warn!("Connection to {} lost: {err}", self.addr);
+
+                self.connected = false;
+
+                Some(Event::Disconnected)
+            }
+            Command::Invalid(err) => {
+                log::warn!("Invalid acquisition from {}: {err}", self.addr);
+
+                self.acquiring = false;
+
+                None
+            }
+            Command::Lost(err) => {
+                log::warn!("Connection to {} lost: {err}", self.addr);
+
+                self.acquiring = false;
+                self.connected = false;
+
+                Some(Event::Disconnected)
+            }
+        }
+    }
+
+    pub fn stop(&self) {
+        if !self.connected {
+            return;
+        }
+
+        self.redpitaya.acquire.stop().ok();
+        self.redpitaya
+            .generator
+            .stop(redpitaya_scpi::generator::Source::OUT1)
+            .ok();
+        self.redpitaya
+            .generator
+            .stop(redpitaya_scpi::generator::Source::OUT2)
+            .ok();
+    }
+
+    fn probe<C>(&self, sender: &relm4::ComponentSender<C>, delay: std::time::Duration)
+    where
+        C: relm4::Component,
+        C::CommandOutput: From<Command>,
+    {
+        let addr = self.addr.clone();
+
+        sender.spawn_oneshot_command(move || {
+            std::thread::sleep(delay);
+
+            Command::Connection(crate::connection::identify(&addr).map_err(|err| err.to_string()))
+                .into()
+        });
+    }
+}
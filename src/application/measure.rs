@@ -0,0 +1,253 @@
+use gtk::prelude::*;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Statistic {
+    #[default]
+    Current,
+    Minimum,
+    Maximum,
+    Average,
+}
+
+impl Statistic {
+    const ALL: [Self; 4] = [Self::Current, Self::Minimum, Self::Maximum, Self::Average];
+
+    fn reduce(
+        self,
+        history: &[crate::measure::Measurements],
+    ) -> Option<crate::measure::Measurements> {
+        if history.is_empty() {
+            return None;
+        }
+
+        let measurements = match self {
+            Self::Current => history[history.len() - 1],
+            Self::Minimum => crate::measure::Measurements::min(history),
+            Self::Maximum => crate::measure::Measurements::max(history),
+            Self::Average => crate::measure::Measurements::mean(history),
+        };
+
+        Some(measurements)
+    }
+}
+
+impl std::fmt::Display for Statistic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let display = match self {
+            Self::Current => "Current",
+            Self::Minimum => "Minimum",
+            Self::Maximum => "Maximum",
+            Self::Average => "Average",
+        };
+
+        write!(f, "{display}")
+    }
+}
+
+#[derive(Debug)]
+pub enum InputMsg {
+    Clear(String),
+    Length(usize),
+    Statistic(u32),
+    Update(String, crate::measure::Measurements),
+}
+
+pub struct Model {
+    columns: std::collections::BTreeMap<String, Vec<gtk::Label>>,
+    history: std::collections::BTreeMap<
+        String,
+        std::collections::VecDeque<crate::measure::Measurements>,
+    >,
+    length: usize,
+    statistic: Statistic,
+}
+
+#[relm4::component(pub)]
+impl relm4::Component for Model {
+    type CommandOutput = ();
+    type Init = ();
+    type Input = InputMsg;
+    type Output = ();
+
+    fn init(
+        _: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        let statistics = Statistic::ALL.map(|statistic| statistic.to_string());
+        let statistics = statistics.each_ref().map(String::as_str);
+
+        let model = Self {
+            columns: std::collections::BTreeMap::new(),
+            history: std::collections::BTreeMap::new(),
+            length: 10,
+            statistic: Statistic::default(),
+        };
+
+        let widgets = view_output!();
+
+        for (row, (name, _)) in crate::measure::NAMES.iter().enumerate() {
+            let label = gtk::Label::new(Some(name));
+            label.set_xalign(0.0);
+            widgets.grid.attach(&label, 0, row as i32 + 1, 1, 1);
+        }
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut ModelWidgets,
+        msg: Self::Input,
+        _: relm4::ComponentSender<Self>,
+        _: &Self::Root,
+    ) {
+        match msg {
+            InputMsg::Clear(name) => {
+                self.history.remove(&name);
+
+                if let Some(labels) = self.columns.remove(&name) {
+                    for label in &labels {
+                        widgets.grid.remove(label);
+                    }
+
+                    self.layout(widgets);
+                }
+            }
+            InputMsg::Length(length) => {
+                self.length = length.max(1);
+
+                for history in self.history.values_mut() {
+                    while history.len() > self.length {
+                        history.pop_front();
+                    }
+                }
+
+                self.refresh_all();
+            }
+            InputMsg::Statistic(statistic) => {
+                self.statistic = Statistic::ALL[statistic as usize % Statistic::ALL.len()];
+                self.refresh_all();
+            }
+            InputMsg::Update(name, measurements) => {
+                let history = self.history.entry(name.clone()).or_default();
+
+                history.push_back(measurements);
+
+                while history.len() > self.length {
+                    history.pop_front();
+                }
+
+                if !self.columns.contains_key(&name) {
+                    self.columns.insert(name.clone(), column(&name));
+                    self.layout(widgets);
+                }
+
+                self.refresh(&name);
+            }
+        }
+    }
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: 10,
+
+            gtk::Frame {
+                set_label: Some("Statistics"),
+
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Horizontal,
+                    set_spacing: 5,
+
+                    gtk::DropDown::from_strings(&statistics) {
+                        connect_selected_notify[sender] => move |this| {
+                            sender.input(InputMsg::Statistic(this.selected()));
+                        },
+                    },
+                    gtk::Label {
+                        set_label: "over last",
+                    },
+                    gtk::SpinButton::with_range(1.0, 1_000.0, 1.0) {
+                        set_value: 10.0,
+
+                        connect_value_changed[sender] => move |this| {
+                            sender.input(InputMsg::Length(this.value() as usize));
+                        },
+                    },
+                    gtk::Label {
+                        set_label: "acquisitions",
+                    },
+                },
+            },
+            #[name = "grid"]
+            gtk::Grid {
+                set_column_spacing: 10,
+                set_row_spacing: 5,
+            },
+        }
+    }
+}
+
+impl Model {
+    fn layout(&self, widgets: &ModelWidgets) {
+        for (column, labels) in self.columns.values().enumerate() {
+            for (row, label) in labels.iter().enumerate() {
+                if label.parent().is_some() {
+                    widgets.grid.remove(label);
+                }
+
+                widgets
+                    .grid
+                    .attach(label, column as i32 + 1, row as i32, 1, 1);
+            }
+        }
+    }
+
+    fn refresh_all(&self) {
+        for name in self.columns.keys() {
+            self.refresh(name);
+        }
+    }
+
+    fn refresh(&self, name: &str) {
+        let (Some(history), Some(labels)) = (self.history.get(name), self.columns.get(name)) else {
+            return;
+        };
+
+        let history = history.iter().copied().collect::<Vec<_>>();
+        let Some(measurements) = self.statistic.reduce(&history) else {
+            return;
+        };
+
+        for (((_, unit), value), label) in crate::measure::NAMES
+            .iter()
+            .zip(measurements.values)
+            .zip(labels.iter().skip(1))
+        {
+            let text = if value.is_nan() {
+                "—".to_string()
+            } else if *unit == "%" {
+                format!("{value:.1} %")
+            } else {
+                crate::scales::format(value, unit)
+            };
+
+            label.set_label(&text);
+        }
+    }
+}
+
+fn column(name: &str) -> Vec<gtk::Label> {
+    let header = gtk::Label::new(Some(name));
+    header.set_xalign(1.0);
+
+    std::iter::once(header)
+        .chain(crate::measure::NAMES.iter().map(|_| {
+            let label = gtk::Label::new(None);
+            label.add_css_class("monospace");
+            label.set_xalign(1.0);
+            label
+        }))
+        .collect()
+}
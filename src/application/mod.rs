@@ -1,54 +1,101 @@
 mod acquire;
+mod board;
+mod bode;
+pub mod connection;
+mod cursors;
 mod generator;
 mod graph;
+mod link;
+mod measure;
+mod reference;
+mod session;
 mod trigger;
 
+use crate::color::Colorable as _;
 use gtk::prelude::*;
 use relm4::ComponentController as _;
 
 macro_rules! redraw {
-    ($self:ident, $widget:ident, $image:ident) => {{
-        let context = gtk::cairo::Context::new(&$image)?;
-
-        if $image.width() > 0 && $image.height() > 0 {
-            $self.transform(
-                $self.data.scales,
-                &context,
-                $image.width() as f64,
-                $image.height() as f64,
-            );
+    ($self:ident, $widget:ident, $surface:ident, $width:ident, $height:ident) => {{
+        let context = gtk::cairo::Context::new($surface)?;
+
+        if $width > 0.0 && $height > 0.0 {
+            $self.transform($self.data.scales, &context, $width, $height);
             context.set_line_width(0.01);
 
-            $self.$widget.emit($widget::InputMsg::Redraw(
-                Box::new(context.clone()),
-                Box::new($self.data.clone()),
-            ));
+            $self.$widget.model().draw(&context, &$self.data)?;
         }
     }};
 }
 
+#[derive(Debug)]
+pub enum Command {
+    Board(String, Option<session::Board>, Result<String, String>),
+    Link(link::Command),
+}
+
+impl From<link::Command> for Command {
+    fn from(command: link::Command) -> Self {
+        Self::Link(command)
+    }
+}
+
 #[derive(Debug)]
 pub enum Msg {
     Acquire(acquire::OutputMsg),
+    AddBoard,
+    Board(usize, board::OutputMsg),
+    Bode(bode::OutputMsg),
+    Cursors(cursors::OutputMsg),
     Generator(generator::OutputMsg),
     Graph(graph::OutputMsg),
+    Reference(reference::OutputMsg),
     Trigger(trigger::OutputMsg),
     Draw,
+    HDiv(u32),
+    LoadSession(std::path::PathBuf),
+    OpenSession,
+    SaveImage,
+    SaveImageTo(std::path::PathBuf),
+    SaveSession,
+    SaveSessionTo(std::path::PathBuf),
     Quit,
+    ResetView,
+    VDiv(u32),
 }
 
 pub struct Model {
+    acquisitions: std::collections::VecDeque<std::time::Instant>,
+    board_addr: gtk::EntryBuffer,
+    boards: Vec<(usize, relm4::Controller<board::Model>)>,
+    bode: relm4::Controller<bode::Model>,
     data: Data,
     graph: relm4::Controller<graph::Model>,
+    link: link::Link,
+    measure: relm4::Controller<measure::Model>,
+    offsets: Vec<(String, f64)>,
+    reference: relm4::Controller<reference::Model>,
     acquire: relm4::Controller<acquire::Model>,
+    cursors: relm4::Controller<cursors::Model>,
     generator: relm4::Controller<generator::Model>,
+    image_dialog: relm4::Controller<relm4_components::save_dialog::SaveDialog>,
+    open_session_dialog: relm4::Controller<relm4_components::open_dialog::OpenDialog>,
+    save_session_dialog: relm4::Controller<relm4_components::save_dialog::SaveDialog>,
     trigger: relm4::Controller<trigger::Model>,
+    trigger_delay: u16,
+    trigger_level: f32,
+}
+
+struct Axis {
+    name: String,
+    color: crate::Color,
+    offset: f64,
+    scale: f64,
 }
 
 #[derive(Clone, Debug)]
 struct Data {
     rate: redpitaya_scpi::acquire::SamplingRate,
-    redpitaya: redpitaya_scpi::Redpitaya,
     scales: crate::Scales,
     levels: std::collections::HashMap<String, i32>,
 }
@@ -62,7 +109,7 @@ impl Data {
 
         match self.levels.get(&channel) {
             Some(level) => {
-                if channel == "DELAY" {
+                if orientation(&channel) == graph::level::Orientation::Top {
                     self.scales.x_to_offset(*level)
                 } else {
                     self.scales.y_to_offset(*level)
@@ -73,10 +120,18 @@ impl Data {
     }
 }
 
+fn orientation(name: &str) -> graph::level::Orientation {
+    match name {
+        "DELAY" | "T1" | "T2" => graph::level::Orientation::Top,
+        "TRIG" | "V1" | "V2" => graph::level::Orientation::Right,
+        _ => graph::level::Orientation::Left,
+    }
+}
+
 #[relm4::component(pub)]
 impl relm4::Component for Model {
-    type CommandOutput = ();
-    type Init = redpitaya_scpi::Redpitaya;
+    type CommandOutput = Command;
+    type Init = (crate::redpitaya::Redpitaya, String);
     type Input = Msg;
     type Output = ();
 
@@ -87,57 +142,175 @@ impl relm4::Component for Model {
     ) -> relm4::ComponentParts<Self> {
         crate::Color::init();
 
+        let (init, addr) = init;
+
         let mut scales = crate::Scales {
             h: (0.0, 0.0),
             v: (-5.0, 5.0),
-            n_samples: init.data.buffer_size().unwrap(),
+            duration: 0.0,
+            n_samples: init.data.buffer_size().unwrap_or(16_384),
             window: crate::scales::Rect {
                 width: 0,
                 height: 0,
             },
         };
 
-        let rate = init.acquire.decimation().unwrap().into();
+        let rate = init
+            .acquire
+            .decimation()
+            .unwrap_or(redpitaya_scpi::acquire::Decimation::DEC_1)
+            .into();
         scales.with_sampling_rate(rate);
 
         let acquire = acquire::Model::builder()
-            .launch(init.acquire.clone())
+            .launch((init.acquire.clone(), 0))
             .forward(sender.input_sender(), Msg::Acquire);
 
         let generator = generator::Model::builder()
-            .launch(init.generator.clone())
+            .launch((
+                init.generator.clone(),
+                init.burst.clone(),
+                init.socket().clone(),
+            ))
             .forward(sender.input_sender(), Msg::Generator);
 
+        let cursors = cursors::Model::builder()
+            .launch(())
+            .forward(sender.input_sender(), Msg::Cursors);
+
+        let measure = measure::Model::builder().launch(()).detach();
+
+        let reference = reference::Model::builder()
+            .launch(())
+            .forward(sender.input_sender(), Msg::Reference);
+
+        let bode = bode::Model::builder()
+            .launch(init.clone())
+            .forward(sender.input_sender(), Msg::Bode);
+
         let graph = graph::Model::builder()
             .launch(())
             .forward(sender.input_sender(), Msg::Graph);
 
+        let png = gtk::FileFilter::new();
+        png.set_name(Some("PNG"));
+        png.add_pattern("*.png");
+
+        let svg = gtk::FileFilter::new();
+        svg.set_name(Some("SVG"));
+        svg.add_pattern("*.svg");
+
+        let image_dialog = relm4_components::save_dialog::SaveDialog::builder()
+            .transient_for_native(&root)
+            .launch(relm4_components::save_dialog::SaveDialogSettings {
+                filters: vec![png, svg],
+                ..Default::default()
+            })
+            .connect_receiver(gtk::glib::clone!(
+                #[strong]
+                sender,
+                move |_, response| {
+                    if let relm4_components::save_dialog::SaveDialogResponse::Accept(path) =
+                        response
+                    {
+                        sender.input(Msg::SaveImageTo(path));
+                    }
+                }
+            ));
+
+        let json = gtk::FileFilter::new();
+        json.set_name(Some("JSON"));
+        json.add_pattern("*.json");
+
+        let open_session_dialog = relm4_components::open_dialog::OpenDialog::builder()
+            .transient_for_native(&root)
+            .launch(relm4_components::open_dialog::OpenDialogSettings {
+                filters: vec![json.clone()],
+                ..Default::default()
+            })
+            .connect_receiver(gtk::glib::clone!(
+                #[strong]
+                sender,
+                move |_, response| {
+                    if let relm4_components::open_dialog::OpenDialogResponse::Accept(path) =
+                        response
+                    {
+                        sender.input(Msg::LoadSession(path));
+                    }
+                }
+            ));
+
+        let save_session_dialog = relm4_components::save_dialog::SaveDialog::builder()
+            .transient_for_native(&root)
+            .launch(relm4_components::save_dialog::SaveDialogSettings {
+                filters: vec![json],
+                ..Default::default()
+            })
+            .connect_receiver(gtk::glib::clone!(
+                #[strong]
+                sender,
+                move |_, response| {
+                    if let relm4_components::save_dialog::SaveDialogResponse::Accept(path) =
+                        response
+                    {
+                        sender.input(Msg::SaveSessionTo(path));
+                    }
+                }
+            ));
+
+        let trigger_delay = init.trigger.delay().unwrap_or_default();
+        let trigger_level = init.trigger.level().unwrap_or_default();
+
         let trigger = trigger::Model::builder()
             .launch(init.trigger.clone())
             .forward(sender.input_sender(), Msg::Trigger);
 
+        let v_divs = crate::scales::steps(crate::scales::V_DIV)
+            .iter()
+            .map(|div| crate::scales::format(*div, "V"))
+            .collect::<Vec<_>>();
+        let v_divs = v_divs.iter().map(String::as_str).collect::<Vec<_>>();
+
+        let h_divs = crate::scales::steps(crate::scales::H_DIV)
+            .iter()
+            .map(|div| crate::scales::format(*div / 1_000_000.0, "s"))
+            .collect::<Vec<_>>();
+        let h_divs = h_divs.iter().map(String::as_str).collect::<Vec<_>>();
+
         let mut model = Self {
             data: Data {
                 rate,
-                redpitaya: init,
                 scales,
                 levels: std::collections::HashMap::new(),
             },
             acquire,
+            acquisitions: std::collections::VecDeque::new(),
+            board_addr: gtk::EntryBuffer::new(None::<&str>),
+            boards: Vec::new(),
+            bode,
+            cursors,
             generator,
             graph,
+            image_dialog,
+            link: link::Link::new(init, addr, &sender),
+            measure,
+            offsets: Vec::new(),
+            reference,
+            open_session_dialog,
+            save_session_dialog,
             trigger,
+            trigger_delay,
+            trigger_level,
         };
 
         let widgets = view_output!();
 
-        model
-            .data
-            .redpitaya
-            .data
-            .set_units(redpitaya_scpi::data::Unit::VOLTS);
+        if let Ok(session) = session::Session::load(&session::Session::default_path()) {
+            model.restore(&sender, session);
+        }
 
-        model.data.redpitaya.acquire.start();
+        model.update_divs(&widgets);
+        model.update_connection(&widgets);
 
         relm4::ComponentParts { model, widgets }
     }
@@ -150,45 +323,350 @@ impl relm4::Component for Model {
         _: &Self::Root,
     ) {
         match msg {
-            Msg::Draw => self.draw(widgets).unwrap(),
+            Msg::Draw if !self.link.is_connected() => (),
+            Msg::Draw => {
+                self.cursors
+                    .emit(cursors::InputMsg::Readout(self.readout()));
+                self.draw(widgets).unwrap();
+            }
+            Msg::AddBoard => {
+                let addr = self.board_addr.text().to_string();
+
+                self.add_board(&sender, addr, None);
+            }
+            Msg::Board(id, msg) => match msg {
+                board::OutputMsg::Acquire(acquire::OutputMsg::Export(path)) => {
+                    if let Some((_, board)) = self.boards.iter().find(|(x, _)| *x == id)
+                        && let Err(err) = board.model().export(&path, &self.data)
+                    {
+                        log::error!("Unable to export {}: {err}", path.display());
+                    }
+                }
+                board::OutputMsg::Acquire(acquire::OutputMsg::Rate(rate)) => {
+                    if rate != self.data.rate
+                        && let Some((_, board)) = self.boards.iter().find(|(x, _)| *x == id)
+                    {
+                        log::info!(
+                            "Board #{} samples at {rate}, switching it back to {}",
+                            id + 1,
+                            self.data.rate
+                        );
+                        board.emit(board::InputMsg::Rate(self.data.rate));
+                    }
+                }
+                board::OutputMsg::Acquire(msg) => sender.input(Msg::Acquire(msg)),
+                board::OutputMsg::Draw => sender.input(Msg::Draw),
+                board::OutputMsg::Remove => {
+                    if let Some(index) = self.boards.iter().position(|(x, _)| *x == id) {
+                        let (_, board) = self.boards.remove(index);
+
+                        for axis in board.model().axes(&self.data) {
+                            self.measure
+                                .emit(measure::InputMsg::Clear(axis.name.clone()));
+                            self.graph.emit(graph::InputMsg::SourceStop(
+                                graph::level::Orientation::Left,
+                                axis.name,
+                            ));
+                        }
+
+                        widgets
+                            .notebook
+                            .remove_page(widgets.notebook.page_num(board.widget()));
+                    }
+
+                    self.update_sources();
+                    sender.input(Msg::Draw);
+                }
+            },
+            Msg::Cursors(msg) => {
+                let (names, orientation, enable) = match msg {
+                    cursors::OutputMsg::Time(enable) => {
+                        (["T1", "T2"], graph::level::Orientation::Top, enable)
+                    }
+                    cursors::OutputMsg::Voltage(enable) => {
+                        (["V1", "V2"], graph::level::Orientation::Right, enable)
+                    }
+                };
+
+                for (n, name) in names.iter().enumerate() {
+                    if enable {
+                        self.place_cursor(name, n);
+                        self.graph
+                            .emit(graph::InputMsg::SourceStart(orientation, name.to_string()));
+                    } else {
+                        self.graph
+                            .emit(graph::InputMsg::SourceStop(orientation, name.to_string()));
+                    }
+                }
+
+                sender.input(Msg::Draw);
+            }
+            Msg::HDiv(index) => {
+                let steps = crate::scales::steps(crate::scales::H_DIV);
+
+                if let Some(div) = steps.get(index as usize).copied()
+                    && (div - self.data.scales.h_div()).abs() > div / 1_000.0
+                {
+                    self.rescale(widgets, &sender, |scales| scales.set_h_div(div));
+                }
+            }
+            Msg::VDiv(index) => {
+                let steps = crate::scales::steps(crate::scales::V_DIV);
+
+                if let Some(div) = steps.get(index as usize).copied()
+                    && (div - self.data.scales.v_div()).abs() > div / 1_000.0
+                {
+                    self.rescale(widgets, &sender, |scales| scales.set_v_div(div));
+                }
+            }
+            Msg::ResetView => {
+                let rate = self.data.rate;
+
+                self.rescale(widgets, &sender, |scales| {
+                    scales.v = (-5.0, 5.0);
+                    scales.with_sampling_rate(rate);
+                });
+            }
+            Msg::OpenSession => self
+                .open_session_dialog
+                .emit(relm4_components::open_dialog::OpenDialogMsg::Open),
+            Msg::LoadSession(path) => match session::Session::load(&path) {
+                Ok(session) => {
+                    self.restore(&sender, session);
+                    self.update_divs(widgets);
+                    sender.input(Msg::Draw);
+                }
+                Err(err) => log::error!("Unable to load {}: {err}", path.display()),
+            },
+            Msg::SaveSession => {
+                self.save_session_dialog
+                    .emit(relm4_components::save_dialog::SaveDialogMsg::SaveAs(
+                        "session.json".to_string(),
+                    ))
+            }
+            Msg::SaveSessionTo(path) => {
+                if let Err(err) = self.session().save(&path) {
+                    log::error!("Unable to save {}: {err}", path.display());
+                }
+            }
+            Msg::SaveImage => {
+                self.image_dialog
+                    .emit(relm4_components::save_dialog::SaveDialogMsg::SaveAs(
+                        format!("{}.png", env!("CARGO_PKG_NAME")),
+                    ))
+            }
+            Msg::SaveImageTo(path) => {
+                let width = widgets.image_width.value() as i32;
+                let height = widgets.image_height.value() as i32;
+
+                if let Err(err) = self.save_image(&path, width, height) {
+                    log::error!("Unable to save {}: {err}", path.display());
+                }
+            }
             Msg::Acquire(msg) => match msg {
+                acquire::OutputMsg::Export(path) => {
+                    if let Err(err) = self.acquire.model().export(&path, &self.data) {
+                        log::error!("Unable to export {}: {err}", path.display());
+                    }
+                }
+                acquire::OutputMsg::Measure(source, measurements) => self
+                    .measure
+                    .emit(measure::InputMsg::Update(source, measurements)),
                 acquire::OutputMsg::Rate(rate) => {
-                    self.data.rate = rate;
-                    self.data.scales.with_sampling_rate(rate);
+                    if rate != self.data.rate {
+                        self.data.rate = rate;
+                        self.data.scales.with_sampling_rate(rate);
+                    }
+
+                    self.place_trigger();
+                    self.update_divs(widgets);
                     self.update_status(widgets);
+
+                    for (_, board) in &self.boards {
+                        board.emit(board::InputMsg::Rate(rate));
+                    }
+                }
+                acquire::OutputMsg::Rescale => {
+                    self.place_trigger();
+                    sender.input(Msg::Draw);
+                }
+                acquire::OutputMsg::Start(source) => {
+                    self.graph.emit(graph::InputMsg::SourceStart(
+                        graph::level::Orientation::Left,
+                        source,
+                    ));
+                    self.update_sources();
+                }
+                acquire::OutputMsg::Stop(source) => {
+                    self.measure.emit(measure::InputMsg::Clear(source.clone()));
+                    self.graph.emit(graph::InputMsg::SourceStop(
+                        graph::level::Orientation::Left,
+                        source,
+                    ));
+                    self.update_sources();
+                }
+            },
+            Msg::Reference(msg) => match msg {
+                reference::OutputMsg::Redraw => sender.input(Msg::Draw),
+                reference::OutputMsg::Store(slot, source) => {
+                    let times = (0..self.data.scales.n_samples)
+                        .map(|sample| self.data.scales.sample_to_ms(sample))
+                        .collect::<Vec<_>>();
+                    let signal = self
+                        .acquire
+                        .model()
+                        .signals()
+                        .into_iter()
+                        .chain(
+                            self.boards
+                                .iter()
+                                .flat_map(|(_, board)| board.model().signals()),
+                        )
+                        .chain(self.generator.model().samples(&times))
+                        .find(|(name, _)| *name == source);
+                    let scale = self
+                        .channels()
+                        .iter()
+                        .find(|axis| axis.name == source)
+                        .map_or(1.0, |axis| axis.scale);
+
+                    match signal {
+                        Some((_, data)) => self.reference.emit(reference::InputMsg::Set(
+                            slot,
+                            session::Reference {
+                                source,
+                                sample_period: crate::measure::sample_period(self.data.rate),
+                                scale,
+                                visible: true,
+                                data,
+                            },
+                        )),
+                        None => log::warn!("{source} is not displayed"),
+                    }
                 }
-                acquire::OutputMsg::Start(source) => self.graph.emit(graph::InputMsg::SourceStart(
-                    graph::level::Orientation::Left,
-                    source.to_string(),
-                )),
-                acquire::OutputMsg::Stop(source) => self.graph.emit(graph::InputMsg::SourceStop(
-                    graph::level::Orientation::Left,
-                    source.to_string(),
-                )),
             },
             Msg::Graph(msg) => match msg {
+                graph::OutputMsg::ClearPersistence => {
+                    self.acquire.emit(acquire::InputMsg::ClearPersistence);
+
+                    for (_, board) in &self.boards {
+                        board.emit(board::InputMsg::ClearPersistence);
+                    }
+
+                    sender.input(Msg::Draw);
+                }
+                graph::OutputMsg::Persistence(persistence) => {
+                    self.acquire
+                        .emit(acquire::InputMsg::Persistence(persistence));
+
+                    for (_, board) in &self.boards {
+                        board.emit(board::InputMsg::Persistence(persistence));
+                    }
+
+                    sender.input(Msg::Draw);
+                }
                 graph::OutputMsg::Level(channel, level) => {
-                    self.data.levels.insert(channel, level);
+                    self.data.levels.insert(channel.clone(), level);
+
+                    match channel.as_str() {
+                        "TRIG" => {
+                            let (offset, factor) = self.trigger_scale();
+
+                            self.trigger_level =
+                                ((self.data.offset("TRIG") - offset) / factor) as f32;
+                            self.trigger
+                                .emit(trigger::InputMsg::Level(self.trigger_level));
+
+                            for (_, board) in &self.boards {
+                                board.emit(board::InputMsg::Level(self.trigger_level));
+                            }
+                        }
+                        "DELAY" => {
+                            let delay = self
+                                .data
+                                .scales
+                                .duration_to_samples(self.data.offset("DELAY"));
+
+                            let clamped = delay.clamp(0.0, u16::MAX as f64);
+
+                            self.trigger_delay = clamped as u16;
+                            self.trigger
+                                .emit(trigger::InputMsg::Delay(self.trigger_delay));
+
+                            if clamped != delay {
+                                self.place_trigger();
+                            }
+
+                            for (_, board) in &self.boards {
+                                board.emit(board::InputMsg::Delay(self.trigger_delay));
+                            }
+                        }
+                        _ => self.place_trigger(),
+                    }
+
+                    sender.input(Msg::Draw);
+                }
+                graph::OutputMsg::Pan(dx, dy) => {
+                    self.rescale(widgets, &sender, |scales| scales.pan(dx, dy));
+                }
+                graph::OutputMsg::Zoom(axis, x, y, delta) => {
+                    let zoom_in = delta < 0.0;
+
+                    self.rescale(widgets, &sender, |scales| match axis {
+                        graph::Axis::Horizontal => {
+                            let div = crate::scales::next_step(
+                                crate::scales::H_DIV,
+                                scales.h_div(),
+                                zoom_in,
+                            );
+                            scales.zoom_h(div, scales.x_to_offset(x));
+                        }
+                        graph::Axis::Vertical => {
+                            let div = crate::scales::next_step(
+                                crate::scales::V_DIV,
+                                scales.v_div(),
+                                zoom_in,
+                            );
+                            scales.zoom_v(div, scales.y_to_offset(y));
+                        }
+                    });
                 }
+                graph::OutputMsg::ZoomRect(start, end) => {
+                    if (start.0 - end.0).abs() > 4 && (start.1 - end.1).abs() > 4 {
+                        self.rescale(widgets, &sender, |scales| scales.zoom_rect(start, end));
+                    }
+                }
+                graph::OutputMsg::Redraw => sender.input(Msg::Draw),
                 graph::OutputMsg::Resize(width, height) => {
-                    self.data.scales.window.width = width;
-                    self.data.scales.window.height = height;
-                    sender.input(Msg::Draw);
+                    self.rescale(widgets, &sender, |scales| {
+                        scales.window = crate::scales::Rect { width, height };
+                    });
                 }
             },
+            Msg::Bode(bode::OutputMsg::Finish) => self.generator.emit(
+                generator::InputMsg::Reapply(redpitaya_scpi::generator::Source::OUT1),
+            ),
             Msg::Generator(msg) => match msg {
                 generator::OutputMsg::Start(source) => {
                     self.graph.emit(graph::InputMsg::SourceStart(
                         graph::level::Orientation::Left,
                         source.to_string(),
-                    ))
+                    ));
+                    self.update_sources();
+                }
+                generator::OutputMsg::Stop(source) => {
+                    self.graph.emit(graph::InputMsg::SourceStop(
+                        graph::level::Orientation::Left,
+                        source.to_string(),
+                    ));
+                    self.update_sources();
                 }
-                generator::OutputMsg::Stop(source) => self.graph.emit(graph::InputMsg::SourceStop(
-                    graph::level::Orientation::Left,
-                    source.to_string(),
-                )),
             },
             Msg::Trigger(msg) => match msg {
+                trigger::OutputMsg::Channel => {
+                    self.place_trigger();
+                    sender.input(Msg::Draw);
+                }
                 trigger::OutputMsg::Auto => {
                     self.graph.emit(graph::InputMsg::SourceStop(
                         graph::level::Orientation::Right,
@@ -199,20 +677,7 @@ impl relm4::Component for Model {
                         "DELAY".to_string(),
                     ));
 
-                    self.acquire.emit(acquire::InputMsg::SetData(
-                        redpitaya_scpi::acquire::Source::IN1,
-                        self.data
-                            .redpitaya
-                            .data
-                            .read_all(redpitaya_scpi::acquire::Source::IN1),
-                    ));
-                    self.acquire.emit(acquire::InputMsg::SetData(
-                        redpitaya_scpi::acquire::Source::IN2,
-                        self.data
-                            .redpitaya
-                            .data
-                            .read_all(redpitaya_scpi::acquire::Source::IN2),
-                    ));
+                    self.link.read(&sender, false);
                 }
                 trigger::OutputMsg::Normal => {
                     self.graph.emit(graph::InputMsg::SourceStart(
@@ -224,20 +689,7 @@ impl relm4::Component for Model {
                         "DELAY".to_string(),
                     ));
 
-                    self.acquire.emit(acquire::InputMsg::SetData(
-                        redpitaya_scpi::acquire::Source::IN1,
-                        self.data
-                            .redpitaya
-                            .data
-                            .read_oldest(redpitaya_scpi::acquire::Source::IN1, 16_384),
-                    ));
-                    self.acquire.emit(acquire::InputMsg::SetData(
-                        redpitaya_scpi::acquire::Source::IN2,
-                        self.data
-                            .redpitaya
-                            .data
-                            .read_oldest(redpitaya_scpi::acquire::Source::IN2, 16_384),
-                    ));
+                    self.link.read(&sender, true);
                 }
                 trigger::OutputMsg::Single => {
                     self.graph.emit(graph::InputMsg::SourceStart(
@@ -249,35 +701,114 @@ impl relm4::Component for Model {
                         "DELAY".to_string(),
                     ));
 
+                    self.link.read(&sender, false);
+                }
+            },
+            Msg::Quit => {
+                let path = session::Session::default_path();
+                if let Err(err) = self.session().save(&path) {
+                    log::error!("Unable to save {}: {err}", path.display());
+                }
+
+                for (_, board) in &self.boards {
+                    board.emit(board::InputMsg::Stop);
+                }
+
+                self.link.stop();
+                relm4::main_application().quit();
+            }
+        };
+    }
+
+    fn update_cmd_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::CommandOutput,
+        sender: relm4::ComponentSender<Self>,
+        _: &Self::Root,
+    ) {
+        match msg {
+            Command::Board(addr, restore, Ok(identity)) => {
+                log::info!("Connected to {identity} at {addr}");
+
+                let Some(id) = (1..=crate::color::BOARDS.len())
+                    .find(|id| self.boards.iter().all(|(x, _)| x != id))
+                else {
+                    log::error!(
+                        "Unable to add {addr}: at most {} boards can be added",
+                        crate::color::BOARDS.len()
+                    );
+                    return;
+                };
+
+                let board = board::Model::builder()
+                    .launch((id, addr.clone()))
+                    .forward(sender.input_sender(), move |msg| Msg::Board(id, msg));
+
+                board.emit(board::InputMsg::Level(self.trigger_level));
+                board.emit(board::InputMsg::Delay(self.trigger_delay));
+                if let Some(restore) = restore {
+                    board.emit(board::InputMsg::Restore(restore));
+                }
+                board.emit(board::InputMsg::Rate(self.data.rate));
+                board.emit(board::InputMsg::Persistence(
+                    self.graph.model().persistence(),
+                ));
+
+                widgets.notebook.append_page(
+                    board.widget(),
+                    Some(&gtk::Label::new(Some(&format!("Board #{}", id + 1)))),
+                );
+                widgets.add_board.popdown();
+
+                self.boards.push((id, board));
+            }
+            Command::Board(addr, _, Err(err)) => {
+                log::error!("Unable to connect to {addr}: {err}");
+            }
+            Command::Link(command) => match self.link.update(command, &sender) {
+                Some(link::Event::Acquired(in1, in2)) => {
+                    self.acquisitions.push_back(std::time::Instant::now());
+                    if self.acquisitions.len() > 10 {
+                        self.acquisitions.pop_front();
+                    }
+
                     self.acquire.emit(acquire::InputMsg::SetData(
                         redpitaya_scpi::acquire::Source::IN1,
-                        self.data
-                            .redpitaya
-                            .data
-                            .read_all(redpitaya_scpi::acquire::Source::IN1),
+                        in1,
                     ));
                     self.acquire.emit(acquire::InputMsg::SetData(
                         redpitaya_scpi::acquire::Source::IN2,
-                        self.data
-                            .redpitaya
-                            .data
-                            .read_all(redpitaya_scpi::acquire::Source::IN2),
+                        in2,
                     ));
+
+                    self.update_status(widgets);
+                    sender.input(Msg::Draw);
                 }
+                Some(link::Event::Connected) => {
+                    self.acquire.emit(acquire::InputMsg::Reapply);
+                    self.trigger
+                        .emit(trigger::InputMsg::Level(self.trigger_level));
+                    self.trigger
+                        .emit(trigger::InputMsg::Delay(self.trigger_delay));
+                    self.trigger.emit(trigger::InputMsg::Reapply);
+                    self.generator.emit(generator::InputMsg::Reapply(
+                        redpitaya_scpi::generator::Source::OUT1,
+                    ));
+                    self.generator.emit(generator::InputMsg::Reapply(
+                        redpitaya_scpi::generator::Source::OUT2,
+                    ));
+                    self.update_connection(widgets);
+                    sender.input(Msg::Draw);
+                }
+                Some(link::Event::Disconnected) => {
+                    self.bode.emit(bode::InputMsg::Abort);
+                    self.generator.emit(generator::InputMsg::Abort);
+                    self.update_connection(widgets);
+                }
+                None => (),
             },
-            Msg::Quit => {
-                self.data.redpitaya.acquire.stop();
-                self.data
-                    .redpitaya
-                    .generator
-                    .stop(redpitaya_scpi::generator::Source::OUT1);
-                self.data
-                    .redpitaya
-                    .generator
-                    .stop(redpitaya_scpi::generator::Source::OUT2);
-                relm4::main_application().quit();
-            }
-        };
+        }
     }
 
     view! {
@@ -289,6 +820,91 @@ impl relm4::Component for Model {
                 gtk::glib::Propagation::Stop
             },
 
+            #[wrap(Some)]
+            set_titlebar = &gtk::HeaderBar {
+                #[name = "open_session"]
+                pack_start = &gtk::Button {
+                    set_label: "Open session",
+
+                    connect_clicked[sender] => move |_| {
+                        sender.input(Msg::OpenSession);
+                    }
+                },
+                #[name = "save_session"]
+                pack_start = &gtk::Button {
+                    set_label: "Save session",
+
+                    connect_clicked[sender] => move |_| {
+                        sender.input(Msg::SaveSession);
+                    }
+                },
+                #[name = "add_board"]
+                pack_start = &gtk::MenuButton {
+                    set_label: "Add board",
+
+                    #[wrap(Some)]
+                    set_popover = &gtk::Popover {
+                        gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 5,
+
+                            gtk::Label {
+                                set_label: "Red Pitaya address",
+                            },
+                            gtk::Entry {
+                                set_buffer: &model.board_addr,
+                                set_placeholder_text: Some("rp-xxxxxx.local:5000"),
+
+                                connect_activate[sender] => move |_| {
+                                    sender.input(Msg::AddBoard);
+                                },
+                            },
+                            gtk::Button {
+                                set_label: "Connect",
+
+                                connect_clicked[sender] => move |_| {
+                                    sender.input(Msg::AddBoard);
+                                }
+                            },
+                        },
+                    },
+                },
+                #[name = "save_image"]
+                pack_end = &gtk::MenuButton {
+                    set_label: "Save image",
+
+                    #[wrap(Some)]
+                    set_popover = &gtk::Popover {
+                        gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 5,
+
+                            gtk::Label {
+                                set_label: "Width (px)",
+                            },
+                            #[name = "image_width"]
+                            gtk::SpinButton::with_range(16.0, 16_384.0, 1.0) {
+                                set_value: 1920.0,
+                            },
+                            gtk::Label {
+                                set_label: "Height (px)",
+                            },
+                            #[name = "image_height"]
+                            gtk::SpinButton::with_range(16.0, 16_384.0, 1.0) {
+                                set_value: 1080.0,
+                            },
+                            gtk::Button {
+                                set_label: "Save",
+
+                                connect_clicked[sender] => move |_| {
+                                    sender.input(Msg::SaveImage);
+                                }
+                            },
+                        },
+                    },
+                },
+            },
+
             #[name = "main_box"]
             gtk::Box {
                 set_orientation: gtk::Orientation::Horizontal,
@@ -300,6 +916,7 @@ impl relm4::Component for Model {
                     set_orientation: gtk::Orientation::Vertical,
                     set_spacing: 0,
 
+                    #[name = "notebook"]
                     gtk::Notebook {
                         set_scrollable: true,
                         set_vexpand: true,
@@ -307,9 +924,54 @@ impl relm4::Component for Model {
                         append_page: (model.acquire.widget(), Some(&gtk::Label::new(Some("Acquire")))),
                         append_page: (model.generator.widget(), Some(&gtk::Label::new(Some("Generator")))),
                         append_page: (model.trigger.widget(), Some(&gtk::Label::new(Some("Trigger")))),
+                        append_page: (model.measure.widget(), Some(&gtk::Label::new(Some("Measure")))),
+                        append_page: (model.cursors.widget(), Some(&gtk::Label::new(Some("Cursors")))),
+                        append_page: (model.reference.widget(), Some(&gtk::Label::new(Some("Reference")))),
+                        append_page: (model.bode.widget(), Some(&gtk::Label::new(Some("Bode")))),
                     },
-                    #[name = "status_bar"]
-                    gtk::Statusbar {
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Horizontal,
+                        set_spacing: 5,
+                        set_margin_all: 5,
+
+                        gtk::Label {
+                            set_label: "V/div",
+                        },
+                        #[name = "v_div"]
+                        gtk::DropDown::from_strings(&v_divs) {
+                            connect_selected_notify[sender] => move |dropdown| {
+                                sender.input(Msg::VDiv(dropdown.selected()));
+                            },
+                        },
+                        gtk::Label {
+                            set_label: "Time/div",
+                        },
+                        #[name = "h_div"]
+                        gtk::DropDown::from_strings(&h_divs) {
+                            connect_selected_notify[sender] => move |dropdown| {
+                                sender.input(Msg::HDiv(dropdown.selected()));
+                            },
+                        },
+                        gtk::Button {
+                            set_label: "Reset",
+                            set_tooltip_text: Some("Scroll to zoom time, Ctrl+scroll to zoom voltage, drag to pan, right-drag to zoom on a region"),
+
+                            connect_clicked[sender] => move |_| {
+                                sender.input(Msg::ResetView);
+                            },
+                        },
+                    },
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Horizontal,
+
+                        #[name = "connection"]
+                        gtk::Label {
+                            set_margin_start: 5,
+                        },
+                        #[name = "status_bar"]
+                        gtk::Statusbar {
+                            set_hexpand: true,
+                        },
                     },
                 },
             },
@@ -318,17 +980,330 @@ impl relm4::Component for Model {
 }
 
 impl Model {
-    fn update_status(&self, widgets: &ModelWidgets) {
-        let status = format!(
-            "{} - {} V/div - {} µs/div",
+    fn status(&self) -> String {
+        format!(
+            "{} - {}/div - {}/div - {:.1} fps",
             self.data.rate,
+            crate::scales::format(self.data.scales.v_div(), "V"),
+            crate::scales::format(self.data.scales.h_div() / 1_000_000.0, "s"),
+            self.fps(),
+        )
+    }
+
+    fn fps(&self) -> f64 {
+        match (self.acquisitions.front(), self.acquisitions.back()) {
+            (Some(first), Some(last)) if first != last => {
+                (self.acquisitions.len() - 1) as f64 / (*last - *first).as_secs_f64()
+            }
+            _ => 0.0,
+        }
+    }
+
+    fn update_connection(&self, widgets: &ModelWidgets) {
+        let connected = self.link.is_connected();
+
+        widgets.connection.set_label(&self.link.label());
+        widgets.main_box.set_sensitive(connected);
+        widgets.open_session.set_sensitive(connected);
+        widgets.save_session.set_sensitive(connected);
+        widgets.save_image.set_sensitive(connected);
+    }
+
+    fn update_divs(&self, widgets: &ModelWidgets) {
+        let select = |dropdown: &gtk::DropDown, range, div: f64| {
+            let position = crate::scales::steps(range)
+                .iter()
+                .position(|step| (step - div).abs() <= div / 1_000.0)
+                .map_or(gtk::INVALID_LIST_POSITION, |position| position as u32);
+
+            dropdown.set_selected(position);
+        };
+
+        select(
+            &widgets.v_div,
+            crate::scales::V_DIV,
             self.data.scales.v_div(),
-            self.data.scales.h_div()
         );
+        select(
+            &widgets.h_div,
+            crate::scales::H_DIV,
+            self.data.scales.h_div(),
+        );
+    }
+
+    fn rescale<F>(&mut self, widgets: &ModelWidgets, sender: &relm4::ComponentSender<Self>, f: F)
+    where
+        F: FnOnce(&mut crate::Scales),
+    {
+        let offsets = self.offsets();
+
+        f(&mut self.data.scales);
+
+        self.place_levels(offsets);
+        self.place_trigger();
+        self.update_divs(widgets);
+        sender.input(Msg::Draw);
+    }
+
+    fn update_status(&self, widgets: &ModelWidgets) {
+        widgets.status_bar.push(
+            widgets.status_bar.context_id("sampling-rate"),
+            &self.status(),
+        );
+    }
+
+    fn offsets(&self) -> Vec<(String, f64)> {
+        let window = self.data.scales.window;
 
-        widgets
-            .status_bar
-            .push(widgets.status_bar.context_id("sampling-rate"), &status);
+        if window.width <= 0 || window.height <= 0 {
+            return self.offsets.clone();
+        }
+
+        self.data
+            .levels
+            .keys()
+            .filter(|name| *name != "TRIG" && *name != "DELAY")
+            .map(|name| (name.clone(), self.data.offset(name)))
+            .collect()
+    }
+
+    fn place_levels(&mut self, offsets: Vec<(String, f64)>) {
+        let window = self.data.scales.window;
+
+        if window.width <= 0 || window.height <= 0 {
+            self.offsets = offsets;
+            return;
+        }
+
+        self.offsets.clear();
+
+        for (name, offset) in offsets {
+            let orientation = orientation(&name);
+            let level = if orientation == graph::level::Orientation::Top {
+                self.data.scales.offset_to_x(offset)
+            } else {
+                self.data.scales.offset_to_y(offset)
+            };
+
+            self.data.levels.insert(name.clone(), level);
+            self.graph
+                .emit(graph::InputMsg::SetLevel(orientation, name, level));
+        }
+    }
+
+    fn session(&self) -> session::Session {
+        let scales = self.data.scales;
+
+        session::Session {
+            acquire: self.acquire.model().session(),
+            generator: self.generator.model().session(),
+            trigger: session::Trigger {
+                level: self.trigger_level,
+                delay: self.trigger_delay,
+                ..self.trigger.model().session()
+            },
+            view: Some(session::View {
+                h: scales.h,
+                v: scales.v,
+            }),
+            levels: self.offsets().into_iter().collect(),
+            references: self.reference.model().session(),
+            bode: self.bode.model().session(),
+            boards: self
+                .boards
+                .iter()
+                .map(|(_, board)| board.model().session())
+                .collect(),
+        }
+    }
+
+    fn add_board(
+        &self,
+        sender: &relm4::ComponentSender<Self>,
+        addr: String,
+        restore: Option<session::Board>,
+    ) {
+        sender.spawn_oneshot_command(move || {
+            let identity = crate::connection::identify(&addr).map_err(|err| err.to_string());
+
+            Command::Board(addr, restore, identity)
+        });
+    }
+
+    fn restore(&mut self, sender: &relm4::ComponentSender<Self>, session: session::Session) {
+        let rate = session.acquire.decimation.into();
+
+        if rate != self.data.rate {
+            self.data.rate = rate;
+            self.data.scales.with_sampling_rate(rate);
+        }
+
+        if let Some(view) = session.view {
+            self.data.scales.h = view.h;
+            self.data.scales.v = view.v;
+        }
+
+        self.bode.emit(bode::InputMsg::Restore(session.bode));
+        self.reference
+            .emit(reference::InputMsg::Restore(session.references));
+        self.acquire
+            .emit(acquire::InputMsg::Restore(session.acquire));
+        self.generator
+            .emit(generator::InputMsg::Restore(session.generator));
+
+        self.trigger_level = session.trigger.level;
+        self.trigger_delay = session.trigger.delay;
+        self.trigger
+            .emit(trigger::InputMsg::Level(self.trigger_level));
+        self.trigger
+            .emit(trigger::InputMsg::Delay(self.trigger_delay));
+        self.trigger
+            .emit(trigger::InputMsg::Restore(session.trigger));
+        self.place_levels(session.levels.into_iter().collect());
+        self.place_trigger();
+
+        for board in session.boards {
+            if self
+                .boards
+                .iter()
+                .all(|(_, x)| x.model().addr() != board.addr)
+            {
+                self.add_board(sender, board.addr.clone(), Some(board));
+            }
+        }
+    }
+
+    fn place_cursor(&mut self, name: &str, n: usize) {
+        if self.data.levels.contains_key(name) {
+            return;
+        }
+
+        let orientation = orientation(name);
+        let size = if orientation == graph::level::Orientation::Top {
+            self.data.scales.window.width
+        } else {
+            self.data.scales.window.height
+        };
+        let level = size * (1 + 2 * n as i32) / 4;
+
+        self.data.levels.insert(name.to_string(), level);
+        self.graph.emit(graph::InputMsg::SetLevel(
+            orientation,
+            name.to_string(),
+            level,
+        ));
+    }
+
+    fn readout(&self) -> String {
+        let cursors = self.cursors.model();
+        let mut lines = Vec::new();
+
+        if cursors.time() {
+            let (t1, t2) = (self.data.offset("T1"), self.data.offset("T2"));
+            let delta = (t2 - t1) / 1_000_000.0;
+
+            lines.push(format!(
+                "T1: {}",
+                crate::scales::format(t1 / 1_000_000.0, "s")
+            ));
+            lines.push(format!(
+                "T2: {}",
+                crate::scales::format(t2 / 1_000_000.0, "s")
+            ));
+            lines.push(format!("Δt: {}", crate::scales::format(delta, "s")));
+            if delta != 0.0 {
+                lines.push(format!(
+                    "1/Δt: {}",
+                    crate::scales::format(1.0 / delta.abs(), "Hz")
+                ));
+            }
+
+            let times = [t1, t2];
+            let samples = self
+                .acquire
+                .model()
+                .samples(&self.data, &times)
+                .into_iter()
+                .chain(
+                    self.boards
+                        .iter()
+                        .flat_map(|(_, board)| board.model().samples(&self.data, &times)),
+                )
+                .chain(self.generator.model().samples(&times));
+
+            for (name, values) in samples {
+                let values = values
+                    .iter()
+                    .map(|value| crate::scales::format(*value, "V"))
+                    .collect::<Vec<_>>();
+
+                lines.push(format!("{name}: {}", values.join(" | ")));
+            }
+        }
+
+        if cursors.voltage() {
+            let (v1, v2) = (self.data.offset("V1"), self.data.offset("V2"));
+
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+
+            let axes = self
+                .acquire
+                .model()
+                .axes(&self.data)
+                .into_iter()
+                .chain(
+                    self.boards
+                        .iter()
+                        .flat_map(|(_, board)| board.model().axes(&self.data)),
+                )
+                .chain(self.generator.model().axes(&self.data));
+
+            for axis in axes {
+                lines.push(format!(
+                    "{}: V1 {} | V2 {} | ΔV {}",
+                    axis.name,
+                    crate::scales::format((v1 - axis.offset) * axis.scale, "V"),
+                    crate::scales::format((v2 - axis.offset) * axis.scale, "V"),
+                    crate::scales::format((v2 - v1) * axis.scale, "V"),
+                ));
+            }
+        }
+
+        lines.join("\n")
+    }
+
+    fn place_trigger(&mut self) {
+        let scales = self.data.scales;
+
+        let (offset, factor) = self.trigger_scale();
+        let level = scales.offset_to_y(offset + self.trigger_level as f64 * factor);
+        self.data.levels.insert("TRIG".to_string(), level);
+        self.graph.emit(graph::InputMsg::SetLevel(
+            graph::level::Orientation::Right,
+            "TRIG".to_string(),
+            level,
+        ));
+
+        let delay = scales.offset_to_x(scales.samples_to_duration(self.trigger_delay as f64));
+        self.data.levels.insert("DELAY".to_string(), delay);
+        self.graph.emit(graph::InputMsg::SetLevel(
+            graph::level::Orientation::Top,
+            "DELAY".to_string(),
+            delay,
+        ));
+    }
+
+    fn trigger_scale(&self) -> (f64, f64) {
+        let source = match self.trigger.model().channel() {
+            trigger::Channel::CH1 => redpitaya_scpi::acquire::Source::IN1,
+            trigger::Channel::CH2 => redpitaya_scpi::acquire::Source::IN2,
+            trigger::Channel::Ext => return (0.0, 1.0),
+        };
+        let (name, factor) = self.acquire.model().probe(source);
+
+        (self.data.offset(name), factor)
     }
 
     fn draw(&mut self, widgets: &ModelWidgets) -> Result<(), gtk::cairo::Error> {
@@ -340,16 +1315,216 @@ impl Model {
             self.data.scales.window.height,
         )?;
 
-        redraw!(self, graph, image);
-        redraw!(self, trigger, image);
-        redraw!(self, generator, image);
-        redraw!(self, acquire, image);
+        self.render(&image, image.width() as f64, image.height() as f64)?;
 
         self.graph.emit(graph::InputMsg::SetImage(image));
 
         Ok(())
     }
 
+    fn render(
+        &self,
+        surface: &gtk::cairo::Surface,
+        width: f64,
+        height: f64,
+    ) -> Result<(), gtk::cairo::Error> {
+        match self.graph.model().mode() {
+            graph::Mode::Time => (),
+            graph::Mode::Spectrum => {
+                let context = gtk::cairo::Context::new(surface)?;
+                let signals = self
+                    .acquire
+                    .model()
+                    .signals()
+                    .into_iter()
+                    .chain(
+                        self.boards
+                            .iter()
+                            .flat_map(|(_, board)| board.model().signals()),
+                    )
+                    .collect::<Vec<_>>();
+
+                return self.graph.model().draw_spectrum(
+                    &context,
+                    width,
+                    height,
+                    &signals,
+                    crate::measure::sample_period(self.data.rate),
+                );
+            }
+            graph::Mode::Xy => {
+                let context = gtk::cairo::Context::new(surface)?;
+                let mut data = self.data.clone();
+                data.scales.h = data.scales.v;
+
+                let mut curves = Vec::new();
+
+                if let Some((x, y)) = self.acquire.model().xy() {
+                    curves.push((crate::color::IN1, x, y));
+                }
+
+                if self.graph.model().xy_outputs()
+                    && let Some((x, y)) = self.generator.model().xy(&self.data)
+                {
+                    curves.push((crate::color::OUT1, x, y));
+                }
+
+                if width > 0.0 && height > 0.0 {
+                    self.transform(data.scales, &context, width, height);
+                    self.graph.model().draw_xy(&context, &data, curves)?;
+                }
+
+                return Ok(());
+            }
+        }
+
+        redraw!(self, graph, surface, width, height);
+        redraw!(self, trigger, surface, width, height);
+        redraw!(self, generator, surface, width, height);
+        redraw!(self, acquire, surface, width, height);
+        redraw!(self, cursors, surface, width, height);
+
+        if width > 0.0 && height > 0.0 {
+            let context = gtk::cairo::Context::new(surface)?;
+
+            self.transform(self.data.scales, &context, width, height);
+            context.set_line_width(0.01);
+
+            for (_, board) in &self.boards {
+                board.model().draw(&context, &self.data)?;
+            }
+
+            self.reference.model().draw(&context, &self.channels())?;
+        }
+
+        self.axes(surface, width, height)
+    }
+
+    fn update_sources(&self) {
+        let sources = self.channels().into_iter().map(|axis| axis.name).collect();
+
+        self.reference.emit(reference::InputMsg::Sources(sources));
+    }
+
+    fn channels(&self) -> Vec<Axis> {
+        self.acquire
+            .model()
+            .axes(&self.data)
+            .into_iter()
+            .chain(
+                self.boards
+                    .iter()
+                    .flat_map(|(_, board)| board.model().axes(&self.data)),
+            )
+            .chain(self.generator.model().axes(&self.data))
+            .collect()
+    }
+
+    fn axes(
+        &self,
+        surface: &gtk::cairo::Surface,
+        width: f64,
+        height: f64,
+    ) -> Result<(), gtk::cairo::Error> {
+        let context = gtk::cairo::Context::new(surface)?;
+        let scales = self.data.scales;
+        let font_size = (height / 60.0).max(8.0);
+        let margin = font_size / 2.0;
+
+        context.select_font_face(
+            "monospace",
+            gtk::cairo::FontSlant::Normal,
+            gtk::cairo::FontWeight::Normal,
+        );
+        context.set_font_size(font_size);
+
+        let column = context.text_extents("-000.000 mV")?.x_advance() + margin;
+
+        let axes = self.channels();
+
+        for (n, axis) in axes.into_iter().enumerate() {
+            let x = margin + n as f64 * column;
+
+            context.set_color(axis.color);
+
+            for i in 0..=10 {
+                let value = scales.v.1 - scales.height() / 10.0 * i as f64;
+                let label = crate::scales::format((value - axis.offset) * axis.scale, "V");
+                let y =
+                    (height / 10.0 * i as f64 + font_size / 3.0).clamp(font_size, height - margin);
+
+                context.move_to(x, y);
+                context.show_text(&label)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn save_image(
+        &self,
+        path: &std::path::Path,
+        width: i32,
+        height: i32,
+    ) -> Result<(), gtk::cairo::IoError> {
+        let (w, h) = (width as f64, height as f64);
+
+        if path.extension().is_some_and(|extension| extension == "svg") {
+            let surface = gtk::cairo::SvgSurface::new(w, h, Some(path))?;
+
+            self.render(&surface, w, h)?;
+            self.legend(&surface, w, h)?;
+            surface.finish();
+        } else {
+            let image =
+                gtk::cairo::ImageSurface::create(gtk::cairo::Format::ARgb32, width, height)?;
+
+            self.render(&image, w, h)?;
+            self.legend(&image, w, h)?;
+
+            let mut file = std::fs::File::create(path)?;
+            image.write_to_png(&mut file)?;
+        }
+
+        Ok(())
+    }
+
+    fn legend(
+        &self,
+        surface: &gtk::cairo::Surface,
+        width: f64,
+        height: f64,
+    ) -> Result<(), gtk::cairo::Error> {
+        let context = gtk::cairo::Context::new(surface)?;
+        let status = self.status();
+        let margin = height / 100.0;
+
+        context.select_font_face(
+            "monospace",
+            gtk::cairo::FontSlant::Normal,
+            gtk::cairo::FontWeight::Normal,
+        );
+        context.set_font_size(height / 40.0);
+
+        let extents = context.text_extents(&status)?;
+
+        context.set_color(crate::color::BACKGROUND);
+        context.rectangle(
+            width - extents.width() - 3.0 * margin,
+            height - extents.height() - 3.0 * margin,
+            extents.width() + 2.0 * margin,
+            extents.height() + 2.0 * margin,
+        );
+        context.fill()?;
+
+        context.set_color(crate::color::MAIN_SCALE);
+        context.move_to(
+            width - extents.width() - 2.0 * margin,
+            height - 2.0 * margin,
+        );
+        context.show_text(&status)
+    }
+
     fn transform(
         &self,
         scales: crate::Scales,
@@ -362,7 +1537,7 @@ impl Model {
             0.0,
             0.0,
             -height / scales.height(),
-            scales.h.1 * width / scales.width(),
+            -scales.h.0 * width / scales.width(),
             scales.v.1 * height / scales.height(),
         ));
     }
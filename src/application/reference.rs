@@ -0,0 +1,235 @@
+use crate::color::Colorable as _;
+use gtk::prelude::*;
+
+const SLOTS: [&str; 4] = ["REF A", "REF B", "REF C", "REF D"];
+
+#[derive(Debug)]
+pub enum InputMsg {
+    Clear(usize),
+    Restore([Option<crate::application::session::Reference>; 4]),
+    Set(usize, crate::application::session::Reference),
+    Show(usize, bool),
+    Source(u32),
+    Sources(Vec<String>),
+    Store(usize),
+}
+
+#[derive(Debug)]
+pub enum OutputMsg {
+    Redraw,
+    Store(usize, String),
+}
+
+pub struct Model {
+    dropdown: gtk::DropDown,
+    references: [Option<crate::application::session::Reference>; 4],
+    rows: Vec<(gtk::CheckButton, gtk::Label)>,
+    source: String,
+    sources: Vec<String>,
+}
+
+#[relm4::component(pub)]
+impl relm4::SimpleComponent for Model {
+    type Init = ();
+    type Input = InputMsg;
+    type Output = OutputMsg;
+
+    fn init(
+        _: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        let dropdown = gtk::DropDown::from_strings(&[]);
+        dropdown.connect_selected_notify(gtk::glib::clone!(
+            #[strong]
+            sender,
+            move |this| sender.input(InputMsg::Source(this.selected()))
+        ));
+
+        let mut model = Self {
+            dropdown,
+            references: Default::default(),
+            rows: Vec::new(),
+            source: String::new(),
+            sources: Vec::new(),
+        };
+
+        let widgets = view_output!();
+
+        widgets.source.set_child(Some(&model.dropdown));
+
+        for (slot, name) in SLOTS.iter().enumerate() {
+            let row = gtk::Box::new(gtk::Orientation::Horizontal, 5);
+
+            let show = gtk::CheckButton::with_label(name);
+            show.set_sensitive(false);
+            show.connect_toggled(gtk::glib::clone!(
+                #[strong]
+                sender,
+                move |this| sender.input(InputMsg::Show(slot, this.is_active()))
+            ));
+            row.append(&show);
+
+            let label = gtk::Label::new(Some("Empty"));
+            label.set_hexpand(true);
+            label.set_xalign(0.0);
+            row.append(&label);
+
+            let store = gtk::Button::with_label("Store");
+            store.connect_clicked(gtk::glib::clone!(
+                #[strong]
+                sender,
+                move |_| sender.input(InputMsg::Store(slot))
+            ));
+            row.append(&store);
+
+            let clear = gtk::Button::with_label("Clear");
+            clear.connect_clicked(gtk::glib::clone!(
+                #[strong]
+                sender,
+                move |_| sender.input(InputMsg::Clear(slot))
+            ));
+            row.append(&clear);
+
+            widgets.slots.append(&row);
+            model.rows.push((show, label));
+        }
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>) {
+        match msg {
+            InputMsg::Clear(slot) => {
+                self.references[slot] = None;
+                self.refresh(slot);
+                sender.output(OutputMsg::Redraw).ok();
+            }
+            InputMsg::Restore(references) => {
+                self.references = references;
+
+                for slot in 0..SLOTS.len() {
+                    self.refresh(slot);
+                }
+            }
+            InputMsg::Set(slot, reference) => {
+                self.references[slot] = Some(reference);
+                self.refresh(slot);
+                sender.output(OutputMsg::Redraw).ok();
+            }
+            InputMsg::Show(slot, visible) => {
+                if let Some(reference) = &mut self.references[slot] {
+                    reference.visible = visible;
+                }
+
+                sender.output(OutputMsg::Redraw).ok();
+            }
+            InputMsg::Source(source) => {
+                if let Some(source) = self.sources.get(source as usize) {
+                    self.source = source.clone();
+                }
+            }
+            InputMsg::Sources(sources) => {
+                if sources == self.sources {
+                    return;
+                }
+
+                let selected = sources
+                    .iter()
+                    .position(|source| *source == self.source)
+                    .unwrap_or_default();
+                let names = sources.iter().map(String::as_str).collect::<Vec<_>>();
+
+                self.sources = sources;
+                self.dropdown.set_model(Some(&gtk::StringList::new(&names)));
+                self.dropdown.set_selected(selected as u32);
+
+                if let Some(source) = self.sources.get(selected) {
+                    self.source = source.clone();
+                }
+            }
+            InputMsg::Store(slot) => {
+                sender
+                    .output(OutputMsg::Store(slot, self.source.clone()))
+                    .ok();
+            }
+        }
+    }
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: 10,
+
+            #[name = "source"]
+            gtk::Frame {
+                set_label: Some("Source"),
+            },
+            #[name = "slots"]
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 5,
+            },
+        }
+    }
+}
+
+impl Model {
+    pub(super) fn session(&self) -> [Option<crate::application::session::Reference>; 4] {
+        self.references.clone()
+    }
+
+    fn refresh(&self, slot: usize) {
+        let (show, label) = &self.rows[slot];
+
+        match &self.references[slot] {
+            Some(reference) => {
+                label.set_label(&format!(
+                    "{} @ {}",
+                    reference.source,
+                    crate::scales::format(1.0 / reference.sample_period, "Sa/s"),
+                ));
+                show.set_sensitive(true);
+                show.set_active(reference.visible);
+            }
+            None => {
+                label.set_label("Empty");
+                show.set_active(false);
+                show.set_sensitive(false);
+            }
+        }
+    }
+
+    pub(super) fn draw(
+        &self,
+        context: &gtk::cairo::Context,
+        axes: &[crate::application::Axis],
+    ) -> Result<(), gtk::cairo::Error> {
+        context.set_line_width(0.05);
+
+        for reference in self.references.iter().flatten() {
+            if !reference.visible || reference.data.is_empty() {
+                continue;
+            }
+
+            let (offset, scale) = axes
+                .iter()
+                .find(|axis| axis.name == reference.source)
+                .map_or((0.0, reference.scale), |axis| (axis.offset, axis.scale));
+            let period = reference.sample_period * 1_000_000.0;
+            let duration = reference.data.len() as f64 * period;
+            let color: crate::Color = reference.source.clone().into();
+
+            context.set_color(color.dimmed());
+
+            for (sample, value) in reference.data.iter().enumerate() {
+                let x = sample as f64 * period - duration / 2.0;
+
+                context.line_to(x, offset + value / scale);
+            }
+            context.stroke()?;
+        }
+
+        Ok(())
+    }
+}
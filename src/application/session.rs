@@ -0,0 +1,348 @@
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Session {
+    pub acquire: Acquire,
+    pub generator: Generator,
+    pub trigger: Trigger,
+    pub view: Option<View>,
+    pub levels: std::collections::HashMap<String, f64>,
+    pub references: [Option<Reference>; 4],
+    pub bode: Bode,
+    pub boards: Vec<Board>,
+}
+
+impl Session {
+    pub fn default_path() -> std::path::PathBuf {
+        gtk::glib::user_config_dir()
+            .join(env!("CARGO_PKG_NAME"))
+            .join("session.json")
+    }
+
+    pub fn load(path: &std::path::Path) -> std::io::Result<Self> {
+        load(path)
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> std::io::Result<()> {
+        save(self, path)
+    }
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Recent {
+    pub addresses: Vec<String>,
+}
+
+impl Recent {
+    const LEN: usize = 10;
+
+    pub fn default_path() -> std::path::PathBuf {
+        gtk::glib::user_config_dir()
+            .join(env!("CARGO_PKG_NAME"))
+            .join("recent.json")
+    }
+
+    pub fn load(path: &std::path::Path) -> std::io::Result<Self> {
+        load(path)
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> std::io::Result<()> {
+        save(self, path)
+    }
+
+    pub fn push(&mut self, addr: &str) {
+        self.addresses.retain(|x| x != addr);
+        self.addresses.insert(0, addr.to_string());
+        self.addresses.truncate(Self::LEN);
+    }
+}
+
+fn load<T: serde::de::DeserializeOwned>(path: &std::path::Path) -> std::io::Result<T> {
+    let contents = std::fs::read_to_string(path)?;
+
+    serde_json::from_str(&contents).map_err(std::io::Error::other)
+}
+
+fn save<T: serde::Serialize>(value: &T, path: &std::path::Path) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let contents = serde_json::to_string_pretty(value).map_err(std::io::Error::other)?;
+
+    std::fs::write(path, contents)
+}
+
+#[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Bode {
+    pub start: f64,
+    pub stop: f64,
+    pub points: usize,
+    pub amplitude: f64,
+    pub settle: u32,
+}
+
+impl Default for Bode {
+    fn default() -> Self {
+        Self {
+            start: 100.0,
+            stop: 1_000_000.0,
+            points: 50,
+            amplitude: 0.5,
+            settle: 50,
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Acquire {
+    #[serde(with = "scpi")]
+    pub decimation: redpitaya_scpi::acquire::Decimation,
+    pub average: bool,
+    pub in1: Input,
+    pub in2: Input,
+    pub math1: Math,
+    pub math2: Math,
+}
+
+impl Default for Acquire {
+    fn default() -> Self {
+        Self {
+            decimation: redpitaya_scpi::acquire::Decimation::DEC_1,
+            average: false,
+            in1: Input::default(),
+            in2: Input::default(),
+            math1: Math::default(),
+            math2: Math::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Input {
+    pub enable: bool,
+    #[serde(with = "scpi")]
+    pub gain: redpitaya_scpi::acquire::Gain,
+    pub attenuation: u8,
+    pub scale: f64,
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            gain: redpitaya_scpi::acquire::Gain::LV,
+            attenuation: 1,
+            scale: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Math {
+    pub enable: bool,
+    pub operation: super::acquire::math::Operation,
+    pub operand: super::acquire::math::Operand,
+    pub expression: String,
+    pub scale: f64,
+}
+
+impl Default for Math {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            operation: super::acquire::math::Operation::Sum,
+            operand: super::acquire::math::Operand::In1,
+            expression: "in1 - in2".to_string(),
+            scale: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Board {
+    pub addr: String,
+    pub acquire: Acquire,
+    pub generator: Generator,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Generator {
+    pub out1: Output,
+    pub out2: Output,
+    pub link: bool,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Output {
+    pub enable: bool,
+    #[serde(with = "scpi")]
+    pub form: redpitaya_scpi::generator::Form,
+    pub amplitude: f32,
+    pub offset: f32,
+    pub frequency: u32,
+    pub phase: i32,
+    pub duty_cycle: f32,
+    pub scale: f64,
+    pub arbitrary: Vec<f64>,
+    pub burst: Burst,
+    pub sweep: Sweep,
+    pub modulation: Modulation,
+}
+
+impl Default for Output {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            form: redpitaya_scpi::generator::Form::SINE,
+            amplitude: 1.0,
+            offset: 0.0,
+            frequency: 1_000,
+            phase: 0,
+            duty_cycle: 0.5,
+            scale: 1.0,
+            arbitrary: Vec::new(),
+            burst: Burst::default(),
+            sweep: Sweep::default(),
+            modulation: Modulation::default(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Burst {
+    pub enable: bool,
+    pub count: u32,
+    pub repetitions: u32,
+    pub period: u32,
+    pub trigger: super::generator::burst::Trigger,
+}
+
+impl Default for Burst {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            count: 1,
+            repetitions: 1,
+            period: 1_000,
+            trigger: super::generator::burst::Trigger::Immediate,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Modulation {
+    pub kind: super::generator::modulation::Kind,
+    pub carrier: f64,
+    pub frequency: f64,
+    pub depth: f64,
+}
+
+impl Default for Modulation {
+    fn default() -> Self {
+        Self {
+            kind: super::generator::modulation::Kind::Off,
+            carrier: 10_000.0,
+            frequency: 100.0,
+            depth: 50.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Sweep {
+    pub start: f64,
+    pub stop: f64,
+    pub points: usize,
+    pub dwell: u32,
+    pub spacing: super::generator::sweep::Spacing,
+    pub amplitude: bool,
+    pub amplitude_start: f64,
+    pub amplitude_stop: f64,
+}
+
+impl Default for Sweep {
+    fn default() -> Self {
+        Self {
+            start: 100.0,
+            stop: 100_000.0,
+            points: 50,
+            dwell: 200,
+            spacing: super::generator::sweep::Spacing::Logarithmic,
+            amplitude: false,
+            amplitude_start: 0.1,
+            amplitude_stop: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Reference {
+    pub source: String,
+    pub sample_period: f64,
+    pub scale: f64,
+    pub visible: bool,
+    pub data: Vec<f64>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Trigger {
+    pub channel: super::trigger::Channel,
+    pub edge: super::trigger::Edge,
+    pub mode: super::trigger::Mode,
+    pub level: f32,
+    pub delay: u16,
+}
+
+impl Default for Trigger {
+    fn default() -> Self {
+        Self {
+            channel: super::trigger::Channel::CH1,
+            edge: super::trigger::Edge::Positive,
+            mode: super::trigger::Mode::Normal,
+            level: 0.0,
+            delay: 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
+pub struct View {
+    pub h: (f64, f64),
+    pub v: (f64, f64),
+}
+
+mod scpi {
+    use serde::Deserialize as _;
+
+    pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+        T: Copy + Into<String>,
+    {
+        serializer.serialize_str(&(*value).into())
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
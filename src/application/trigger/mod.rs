@@ -8,6 +8,7 @@ pub use mode::Mode;
 
 use crate::color::Colorable;
 use gtk::prelude::*;
+use relm4::ComponentController as _;
 
 #[derive(Debug)]
 pub enum Command {
@@ -19,12 +20,16 @@ pub enum InputMsg {
     Mode(Mode),
     Channel(Channel),
     Edge(Edge),
-    Redraw(Box<gtk::cairo::Context>, Box<crate::application::Data>),
+    Level(f32),
+    Delay(u16),
+    Reapply,
+    Restore(crate::application::session::Trigger),
 }
 
 #[derive(Debug)]
 pub enum OutputMsg {
     Auto,
+    Channel,
     Normal,
     Single,
 }
@@ -36,13 +41,13 @@ pub struct Model {
     edge_widget: relm4::Controller<crate::widget::RadioGroup<Edge>>,
     mode: Mode,
     mode_widget: relm4::Controller<crate::widget::RadioGroup<Mode>>,
-    trigger: redpitaya_scpi::trigger::Trigger,
+    trigger: crate::redpitaya::Trigger,
 }
 
 #[relm4::component(pub)]
 impl relm4::Component for Model {
     type CommandOutput = Command;
-    type Init = redpitaya_scpi::trigger::Trigger;
+    type Init = crate::redpitaya::Trigger;
     type Input = InputMsg;
     type Output = OutputMsg;
 
@@ -51,8 +56,6 @@ impl relm4::Component for Model {
         _root: Self::Root,
         sender: relm4::ComponentSender<Self>,
     ) -> relm4::ComponentParts<Self> {
-        use relm4::ComponentController as _;
-
         let channel_widget = crate::widget::RadioGroup::builder()
             .launch(crate::widget::radio::Options {
                 current: Some(Channel::CH1),
@@ -102,7 +105,7 @@ impl relm4::Component for Model {
             let fut = shutdown
                 .register(async move {
                     loop {
-                        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
                         out.send(Command::InternalTick).ok();
                     }
                 })
@@ -135,7 +138,7 @@ impl relm4::Component for Model {
         &mut self,
         widgets: &mut Self::Widgets,
         msg: Self::Input,
-        _: relm4::ComponentSender<Self>,
+        sender: relm4::ComponentSender<Self>,
         _: &Self::Root,
     ) {
         match msg {
@@ -151,16 +154,35 @@ impl relm4::Component for Model {
             InputMsg::Channel(channel) => {
                 self.channel = Some(channel);
                 if let Some(source) = self.source() {
-                    self.trigger.enable(source);
+                    self.trigger.enable(source).ok();
                 }
+                sender.output(OutputMsg::Channel).ok();
             }
             InputMsg::Edge(edge) => {
                 self.edge = Some(edge);
                 if let Some(source) = self.source() {
-                    self.trigger.enable(source);
+                    self.trigger.enable(source).ok();
+                }
+            }
+            InputMsg::Level(level) => {
+                self.trigger.set_level(level).ok();
+            }
+            InputMsg::Delay(delay) => {
+                self.trigger.set_delay(delay).ok();
+            }
+            InputMsg::Reapply => {
+                if let Some(source) = self.source() {
+                    self.trigger.enable(source).ok();
                 }
             }
-            InputMsg::Redraw(ref context, ref model) => self.draw(context, model).unwrap(),
+            InputMsg::Restore(trigger) => {
+                self.channel_widget
+                    .emit(crate::widget::radio::InputMsg::Set(trigger.channel));
+                self.edge_widget
+                    .emit(crate::widget::radio::InputMsg::Set(trigger.edge));
+                self.mode_widget
+                    .emit(crate::widget::radio::InputMsg::Set(trigger.mode));
+            }
         }
     }
 
@@ -187,6 +209,19 @@ impl relm4::Component for Model {
 }
 
 impl Model {
+    pub(super) fn session(&self) -> crate::application::session::Trigger {
+        crate::application::session::Trigger {
+            channel: self.channel(),
+            edge: self.edge.unwrap_or(Edge::Positive),
+            mode: self.mode,
+            ..Default::default()
+        }
+    }
+
+    pub(super) fn channel(&self) -> Channel {
+        self.channel.unwrap_or(Channel::CH1)
+    }
+
     fn source(&self) -> Option<redpitaya_scpi::trigger::Source> {
         if self.channel == Some(Channel::CH1) && self.edge == Some(Edge::Positive) {
             Some(redpitaya_scpi::trigger::Source::CH1_PE)
@@ -205,7 +240,7 @@ impl Model {
         }
     }
 
-    fn draw(
+    pub(super) fn draw(
         &self,
         context: &gtk::cairo::Context,
         data: &crate::application::Data,
@@ -0,0 +1,122 @@
+const RATES: [redpitaya_scpi::acquire::SamplingRate; 6] = [
+    redpitaya_scpi::acquire::SamplingRate::RATE_125MHz,
+    redpitaya_scpi::acquire::SamplingRate::RATE_15_6MHz,
+    redpitaya_scpi::acquire::SamplingRate::RATE_1_9MHz,
+    redpitaya_scpi::acquire::SamplingRate::RATE_103_8kHz,
+    redpitaya_scpi::acquire::SamplingRate::RATE_15_2kHz,
+    redpitaya_scpi::acquire::SamplingRate::RATE_1_9kHz,
+];
+
+const PERIODS: f64 = 10.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Point {
+    pub frequency: f64,
+    pub gain: f64,
+    pub phase: f64,
+}
+
+pub fn frequencies(start: f64, stop: f64, points: usize) -> Vec<f64> {
+    if points < 2 {
+        return vec![start];
+    }
+
+    (0..points)
+        .map(|i| start * (stop / start).powf(i as f64 / (points - 1) as f64))
+        .collect()
+}
+
+pub fn rate(frequency: f64) -> redpitaya_scpi::acquire::SamplingRate {
+    RATES
+        .into_iter()
+        .find(|rate| rate.buffer_duration().as_secs_f64() * frequency >= PERIODS)
+        .unwrap_or(redpitaya_scpi::acquire::SamplingRate::RATE_1_9kHz)
+}
+
+pub fn measure(frequency: f64, stimulus: &[f64], response: &[f64], period: f64) -> Point {
+    let (stimulus, response) = (
+        bin(stimulus, frequency, period),
+        bin(response, frequency, period),
+    );
+    let ratio = stimulus.0.hypot(stimulus.1);
+    let gain = response.0.hypot(response.1) / ratio;
+    let phase = (response.1.atan2(response.0) - stimulus.1.atan2(stimulus.0)).to_degrees();
+
+    Point {
+        frequency,
+        gain: 20.0 * gain.log10(),
+        phase: wrap(phase),
+    }
+}
+
+pub fn export(path: &std::path::Path, points: &[Point]) -> std::io::Result<()> {
+    let frequencies = points
+        .iter()
+        .map(|point| point.frequency)
+        .collect::<Vec<_>>();
+    let gains = points.iter().map(|point| point.gain).collect::<Vec<_>>();
+    let phases = points.iter().map(|point| point.phase).collect::<Vec<_>>();
+
+    crate::export::csv(
+        path,
+        &[
+            ("frequency (Hz)", &frequencies),
+            ("gain (dB)", &gains),
+            ("phase (deg)", &phases),
+        ],
+    )
+}
+
+fn bin(samples: &[f64], frequency: f64, period: f64) -> (f64, f64) {
+    let cycles = (samples.len() as f64 * period * frequency).floor();
+    let len = if cycles >= 1.0 {
+        ((cycles / (period * frequency)).round() as usize).min(samples.len())
+    } else {
+        samples.len()
+    };
+
+    let omega = 2.0 * std::f64::consts::PI * frequency * period;
+
+    samples[..len]
+        .iter()
+        .enumerate()
+        .fold((0.0, 0.0), |(re, im), (i, sample)| {
+            let angle = omega * i as f64;
+
+            (re + sample * angle.cos(), im - sample * angle.sin())
+        })
+}
+
+fn wrap(phase: f64) -> f64 {
+    let phase = (phase + 180.0).rem_euclid(360.0) - 180.0;
+
+    if phase == -180.0 { 180.0 } else { phase }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn measure() {
+        let (frequency, period) = (1_000.0, 1e-6);
+        let omega = 2.0 * std::f64::consts::PI * frequency * period;
+        let stimulus = (0..16_384)
+            .map(|i| (omega * i as f64).sin())
+            .collect::<Vec<_>>();
+        let response = (0..16_384)
+            .map(|i| 0.5 * (omega * i as f64 - std::f64::consts::FRAC_PI_2).sin())
+            .collect::<Vec<_>>();
+
+        let point = super::measure(frequency, &stimulus, &response, period);
+
+        assert_eq!(point.frequency, frequency);
+        assert!((point.gain + 6.0206).abs() < 1e-3, "{}", point.gain);
+        assert!((point.phase + 90.0).abs() < 1e-6, "{}", point.phase);
+    }
+
+    #[test]
+    fn wrap() {
+        assert_eq!(super::wrap(270.0), -90.0);
+        assert_eq!(super::wrap(-180.0), 180.0);
+        assert_eq!(super::wrap(-450.0), -90.0);
+    }
+}
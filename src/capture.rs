@@ -0,0 +1,210 @@
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum Trigger {
+    Now,
+    Ch1,
+    Ch2,
+    Ext,
+}
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum Edge {
+    Positive,
+    Negative,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum Channel {
+    In1,
+    In2,
+}
+
+impl From<Channel> for redpitaya_scpi::acquire::Source {
+    fn from(channel: Channel) -> Self {
+        match channel {
+            Channel::In1 => Self::IN1,
+            Channel::In2 => Self::IN2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum Format {
+    Csv,
+    Bin,
+}
+
+#[derive(clap::Args)]
+pub struct Opt {
+    #[clap(
+        long,
+        default_value = "1",
+        help = "Decimation: 1, 8, 64, 1024, 8192 or 65536"
+    )]
+    decimation: redpitaya_scpi::acquire::Decimation,
+    #[clap(long, default_value = "LV", help = "Input gain: LV or HV")]
+    gain: redpitaya_scpi::acquire::Gain,
+    #[clap(long, value_enum, default_value = "now")]
+    trigger: Trigger,
+    #[clap(long, value_enum, default_value = "positive")]
+    edge: Edge,
+    #[clap(long, default_value = "0", help = "Trigger level (V)")]
+    level: f32,
+    #[clap(long, default_value = "0", help = "Trigger delay (samples)")]
+    delay: u16,
+    #[clap(long, default_value = "16384")]
+    samples: u32,
+    #[clap(long, value_enum, value_delimiter = ',', default_value = "in1,in2")]
+    channels: Vec<Channel>,
+    #[clap(long, default_value = "1", help = "Probe attenuation")]
+    attenuation: u8,
+    #[clap(long, default_value = "10", help = "Trigger timeout (s)")]
+    timeout: f64,
+    #[clap(long, value_enum, default_value = "csv")]
+    format: Format,
+    #[clap(long, short)]
+    output: std::path::PathBuf,
+}
+
+#[derive(serde::Serialize)]
+struct Sidecar {
+    sampling_rate: f64,
+    decimation: u32,
+    samples: usize,
+    channels: Vec<String>,
+    dtype: &'static str,
+    layout: &'static str,
+    units: &'static str,
+    attenuation: u8,
+    gain: String,
+}
+
+impl Opt {
+    fn source(&self) -> redpitaya_scpi::trigger::Source {
+        use redpitaya_scpi::trigger::Source;
+
+        match (self.trigger, self.edge) {
+            (Trigger::Now, _) => Source::NOW,
+            (Trigger::Ch1, Edge::Positive) => Source::CH1_PE,
+            (Trigger::Ch1, Edge::Negative) => Source::CH1_NE,
+            (Trigger::Ch2, Edge::Positive) => Source::CH2_PE,
+            (Trigger::Ch2, Edge::Negative) => Source::CH2_NE,
+            (Trigger::Ext, Edge::Positive) => Source::EXT_PE,
+            (Trigger::Ext, Edge::Negative) => Source::EXT_NE,
+        }
+    }
+
+    fn decimation(&self) -> u32 {
+        String::from(self.decimation).parse().unwrap_or(1)
+    }
+
+    fn sampling_rate(&self) -> f64 {
+        125_000_000.0 / self.decimation() as f64
+    }
+}
+
+pub fn run(redpitaya: &crate::redpitaya::Redpitaya, opt: &Opt) -> std::io::Result<()> {
+    redpitaya
+        .data
+        .set_units(redpitaya_scpi::data::Unit::VOLTS)?;
+    redpitaya.acquire.set_decimation(opt.decimation)?;
+
+    for channel in &opt.channels {
+        redpitaya.acquire.set_gain((*channel).into(), opt.gain)?;
+    }
+
+    redpitaya.trigger.set_level(opt.level)?;
+    redpitaya.trigger.set_delay(opt.delay)?;
+
+    redpitaya.acquire.start()?;
+    redpitaya.trigger.enable(opt.source())?;
+
+    let data = wait(redpitaya, opt).and_then(|()| read(redpitaya, opt));
+
+    redpitaya.acquire.stop()?;
+
+    save(opt, &data?)
+}
+
+fn wait(redpitaya: &crate::redpitaya::Redpitaya, opt: &Opt) -> std::io::Result<()> {
+    let start = std::time::Instant::now();
+
+    while redpitaya.trigger.state()? != redpitaya_scpi::trigger::State::TD {
+        if start.elapsed().as_secs_f64() > opt.timeout {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "Trigger timeout",
+            ));
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    Ok(())
+}
+
+fn read(redpitaya: &crate::redpitaya::Redpitaya, opt: &Opt) -> std::io::Result<Vec<Vec<f64>>> {
+    opt.channels
+        .iter()
+        .map(|channel| {
+            let mut data = match opt.trigger {
+                Trigger::Now => redpitaya.data.read_all((*channel).into())?,
+                _ => redpitaya.data.read_oldest((*channel).into(), opt.samples)?,
+            };
+
+            data.truncate(opt.samples as usize);
+
+            Ok(data
+                .iter()
+                .map(|sample| sample * opt.attenuation as f64)
+                .collect())
+        })
+        .collect()
+}
+
+fn save(opt: &Opt, data: &[Vec<f64>]) -> std::io::Result<()> {
+    let names = opt
+        .channels
+        .iter()
+        .map(|channel| redpitaya_scpi::acquire::Source::from(*channel).to_string())
+        .collect::<Vec<_>>();
+
+    match opt.format {
+        Format::Csv => {
+            let rate = opt.sampling_rate();
+            let samples = data.iter().map(Vec::len).max().unwrap_or_default();
+            let time = (0..samples)
+                .map(|sample| sample as f64 / rate)
+                .collect::<Vec<_>>();
+            let headers = names
+                .iter()
+                .map(|name| format!("{name} (V)"))
+                .collect::<Vec<_>>();
+
+            let mut columns = vec![("time (s)", time.as_slice())];
+            for (header, data) in headers.iter().zip(data) {
+                columns.push((header, data));
+            }
+
+            crate::export::csv(&opt.output, &columns)
+        }
+        Format::Bin => {
+            let columns = data.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            crate::export::raw(&opt.output, &columns)?;
+
+            let sidecar = Sidecar {
+                sampling_rate: opt.sampling_rate(),
+                decimation: opt.decimation(),
+                samples: data.iter().map(Vec::len).min().unwrap_or_default(),
+                channels: names,
+                dtype: "<f4",
+                layout: "interleaved",
+                units: "V",
+                attenuation: opt.attenuation,
+                gain: opt.gain.to_string(),
+            };
+
+            let file = std::fs::File::create(opt.output.with_extension("json"))?;
+            serde_json::to_writer_pretty(file, &sidecar).map_err(std::io::Error::other)
+        }
+    }
+}
@@ -27,12 +27,21 @@ impl From<redpitaya_scpi::acquire::Source> for Color {
 
 impl From<String> for Color {
     fn from(s: String) -> Color {
+        if let Some((channel, board)) = s.rsplit_once(" #")
+            && let Ok(board) = board.parse::<usize>()
+        {
+            return Self::board(board, channel);
+        }
+
         match s.as_str() {
             "IN 1" => IN1,
             "IN 2" => IN2,
             "OUT 1" => OUT1,
             "OUT 2" => OUT2,
+            "MATH 1" => MATH1,
+            "MATH 2" => MATH2,
             "TRIG" | "DELAY" => TRIGGER,
+            "T1" | "T2" | "V1" | "V2" => CURSOR,
             _ => MAIN_SCALE,
         }
     }
@@ -45,6 +54,28 @@ impl std::fmt::Display for Color {
 }
 
 impl Color {
+    fn board(board: usize, channel: &str) -> Self {
+        let Some([in1, in2, math1, math2]) = board.checked_sub(2).and_then(|n| BOARDS.get(n))
+        else {
+            return channel.to_string().into();
+        };
+
+        match channel {
+            "IN 1" => in1.clone(),
+            "IN 2" => in2.clone(),
+            "MATH 1" => math1.clone(),
+            "MATH 2" => math2.clone(),
+            _ => channel.to_string().into(),
+        }
+    }
+
+    pub fn dimmed(self) -> Self {
+        Self {
+            a: self.a * 0.4,
+            ..self
+        }
+    }
+
     fn to_css(&self) -> String {
         format!(
             ".{} {{ background-color: rgba({}, {}, {}, {}); }}",
@@ -113,6 +144,145 @@ pub const OUT2: Color = Color {
     a: 1.0,
 };
 
+pub const MATH1: Color = Color {
+    name: "math1",
+    r: 0.3,
+    g: 0.6,
+    b: 1.0,
+    a: 1.0,
+};
+
+pub const MATH2: Color = Color {
+    name: "math2",
+    r: 0.6,
+    g: 0.4,
+    b: 1.0,
+    a: 1.0,
+};
+
+pub const BOARDS: [[Color; 4]; 4] = [
+    [
+        Color {
+            name: "in1-2",
+            r: 1.0,
+            g: 0.6,
+            b: 0.6,
+            a: 1.0,
+        },
+        Color {
+            name: "in2-2",
+            r: 0.4,
+            g: 1.0,
+            b: 1.0,
+            a: 1.0,
+        },
+        Color {
+            name: "math1-2",
+            r: 0.7,
+            g: 0.3,
+            b: 0.3,
+            a: 1.0,
+        },
+        Color {
+            name: "math2-2",
+            r: 0.2,
+            g: 0.6,
+            b: 0.6,
+            a: 1.0,
+        },
+    ],
+    [
+        Color {
+            name: "in1-3",
+            r: 0.8,
+            g: 0.6,
+            b: 0.2,
+            a: 1.0,
+        },
+        Color {
+            name: "in2-3",
+            r: 0.6,
+            g: 0.8,
+            b: 0.6,
+            a: 1.0,
+        },
+        Color {
+            name: "math1-3",
+            r: 0.6,
+            g: 0.45,
+            b: 0.15,
+            a: 1.0,
+        },
+        Color {
+            name: "math2-3",
+            r: 0.4,
+            g: 0.55,
+            b: 0.4,
+            a: 1.0,
+        },
+    ],
+    [
+        Color {
+            name: "in1-4",
+            r: 1.0,
+            g: 0.8,
+            b: 1.0,
+            a: 1.0,
+        },
+        Color {
+            name: "in2-4",
+            r: 0.6,
+            g: 0.6,
+            b: 1.0,
+            a: 1.0,
+        },
+        Color {
+            name: "math1-4",
+            r: 0.7,
+            g: 0.5,
+            b: 0.7,
+            a: 1.0,
+        },
+        Color {
+            name: "math2-4",
+            r: 0.35,
+            g: 0.35,
+            b: 0.7,
+            a: 1.0,
+        },
+    ],
+    [
+        Color {
+            name: "in1-5",
+            r: 0.8,
+            g: 1.0,
+            b: 0.4,
+            a: 1.0,
+        },
+        Color {
+            name: "in2-5",
+            r: 1.0,
+            g: 0.9,
+            b: 0.7,
+            a: 1.0,
+        },
+        Color {
+            name: "math1-5",
+            r: 0.55,
+            g: 0.7,
+            b: 0.25,
+            a: 1.0,
+        },
+        Color {
+            name: "math2-5",
+            r: 0.7,
+            g: 0.6,
+            b: 0.45,
+            a: 1.0,
+        },
+    ],
+];
+
 pub const TRIGGER: Color = Color {
     name: "trigger",
     r: 1.0,
@@ -121,6 +291,14 @@ pub const TRIGGER: Color = Color {
     a: 1.0,
 };
 
+pub const CURSOR: Color = Color {
+    name: "cursor",
+    r: 0.0,
+    g: 0.8,
+    b: 1.0,
+    a: 1.0,
+};
+
 impl Color {
     pub fn init() {
         let colors = [
@@ -131,7 +309,10 @@ impl Color {
             IN2,
             OUT1,
             OUT2,
+            MATH1,
+            MATH2,
             TRIGGER,
+            CURSOR,
         ];
         let mut styles = String::new();
 
@@ -139,6 +320,10 @@ impl Color {
             styles.push_str(&color.to_css());
         }
 
+        for color in BOARDS.iter().flatten() {
+            styles.push_str(&color.to_css());
+        }
+
         let provider = gtk::CssProvider::new();
 
         provider.load_from_data(&styles);
@@ -0,0 +1,440 @@
+use std::io::{BufRead as _, Write as _};
+
+pub const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+
+#[derive(Clone, Debug)]
+pub struct Socket {
+    addr: String,
+}
+
+impl Socket {
+    pub fn new(addr: String) -> Self {
+        Self { addr }
+    }
+
+    pub fn send(&self, command: impl std::fmt::Display) -> std::io::Result<()> {
+        self.exchange(&command.to_string(), false).map(drop)
+    }
+
+    pub fn query(&self, command: impl std::fmt::Display) -> std::io::Result<String> {
+        self.exchange(&command.to_string(), true)
+    }
+
+    fn exchange(&self, command: &str, answer: bool) -> std::io::Result<String> {
+        log::info!("> {command}");
+
+        let result = exchange(&self.addr, command, answer);
+
+        match &result {
+            Ok(answer) if !answer.is_empty() => log::debug!("< {answer}"),
+            Ok(_) => (),
+            Err(err) => log::warn!("{command} on {}: {err}", self.addr),
+        }
+
+        result
+    }
+}
+
+pub fn identify(addr: &str) -> std::io::Result<String> {
+    exchange(addr, "*IDN?", true)
+}
+
+fn exchange(addr: &str, command: &str, answer: bool) -> std::io::Result<String> {
+    use std::net::ToSocketAddrs as _;
+
+    let addr = addr
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "unknown address"))?;
+
+    let mut stream = std::net::TcpStream::connect_timeout(&addr, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    stream.write_all(format!("{command}\r\n").as_bytes())?;
+
+    let mut message = String::new();
+
+    if answer {
+        let len = std::io::BufReader::new(stream).read_line(&mut message)?;
+
+        if len == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+    }
+
+    Ok(message.trim_end().to_string())
+}
+
+const PORT: u16 = 5000;
+const MDNS: std::net::SocketAddrV4 =
+    std::net::SocketAddrV4::new(std::net::Ipv4Addr::new(224, 0, 0, 251), 5353);
+const A: u16 = 1;
+const PTR: u16 = 12;
+const SRV: u16 = 33;
+const UNICAST_IN: u16 = 0x8001;
+const SCPI: &str = "_scpi-raw._tcp.local";
+const SERVICES: [&str; 2] = [SCPI, "_workstation._tcp.local"];
+const WORKERS: usize = 64;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Board {
+    pub addr: String,
+    pub hostname: Option<String>,
+    pub identity: Option<String>,
+}
+
+impl std::fmt::Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.hostname {
+            Some(hostname) => write!(f, "{hostname} ({})", self.addr)?,
+            None => f.write_str(&self.addr)?,
+        }
+
+        if let Some(identity) = &self.identity {
+            write!(f, " — {identity}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Answers {
+    hosts: Vec<(String, std::net::Ipv4Addr)>,
+    instances: Vec<(String, String)>,
+    services: Vec<(String, String, u16)>,
+}
+
+impl Answers {
+    fn candidates(
+        &self,
+        from: std::net::Ipv4Addr,
+    ) -> Vec<(std::net::SocketAddrV4, Option<String>)> {
+        let mut candidates = Vec::new();
+
+        for (service, instance) in &self.instances {
+            if !SERVICES.contains(&service.as_str()) {
+                continue;
+            }
+
+            for (_, target, port) in self.services.iter().filter(|(name, ..)| name == instance) {
+                let ip = self
+                    .hosts
+                    .iter()
+                    .find(|(name, _)| name == target)
+                    .map_or(from, |(_, ip)| *ip);
+                let port = if service == SCPI { *port } else { PORT };
+                let hostname = target.clone();
+
+                candidates.push((std::net::SocketAddrV4::new(ip, port), Some(hostname)));
+            }
+        }
+
+        if candidates.is_empty() {
+            let hostname = self
+                .hosts
+                .iter()
+                .find(|(_, ip)| *ip == from)
+                .map(|(name, _)| name.clone());
+
+            candidates.push((std::net::SocketAddrV4::new(from, PORT), hostname));
+        }
+
+        candidates
+    }
+}
+
+pub fn discover() -> std::io::Result<Vec<Board>> {
+    let socket = std::net::UdpSocket::bind("0.0.0.0:0")?;
+    socket.set_read_timeout(Some(TIMEOUT / 4))?;
+    socket.send_to(&query(), MDNS)?;
+
+    let deadline = std::time::Instant::now() + TIMEOUT;
+    let mut candidates = Vec::new();
+    let mut buffer = [0; 9_000];
+
+    while std::time::Instant::now() < deadline {
+        match socket.recv_from(&mut buffer) {
+            Ok((len, std::net::SocketAddr::V4(from))) => {
+                let Some(answers) = parse(&buffer[..len]) else {
+                    log::warn!("Invalid mDNS response from {from}");
+                    continue;
+                };
+
+                for candidate in answers.candidates(*from.ip()) {
+                    if !candidates.iter().any(|(addr, _)| *addr == candidate.0) {
+                        candidates.push(candidate);
+                    }
+                }
+            }
+            Ok(_) => (),
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) => {}
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(probe(&candidates))
+}
+
+pub fn scan(subnet: &str) -> std::io::Result<Vec<Board>> {
+    let invalid = || {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("invalid subnet '{subnet}', expected something like 192.168.1.0/24"),
+        )
+    };
+
+    let (network, prefix) = subnet.split_once('/').ok_or_else(invalid)?;
+    let network = network
+        .parse::<std::net::Ipv4Addr>()
+        .map_err(|_| invalid())?;
+    let prefix = prefix.parse::<u32>().map_err(|_| invalid())?;
+
+    if !(16..=30).contains(&prefix) {
+        return Err(invalid());
+    }
+
+    let mask = u32::MAX << (32 - prefix);
+    let first = u32::from(network) & mask;
+    let last = first | !mask;
+
+    let hosts = ((first + 1)..last)
+        .map(|host| std::net::SocketAddrV4::new(host.into(), PORT))
+        .collect::<Vec<_>>();
+
+    let open = parallel(&hosts, |addr| {
+        std::net::TcpStream::connect_timeout(&(*addr).into(), TIMEOUT / 5)
+            .ok()
+            .map(|_| (*addr, None))
+    });
+
+    Ok(probe(&open))
+}
+
+fn probe(candidates: &[(std::net::SocketAddrV4, Option<String>)]) -> Vec<Board> {
+    parallel(candidates, |(addr, hostname)| {
+        let addr = addr.to_string();
+
+        identify(&addr).ok().map(|identity| Board {
+            addr,
+            hostname: hostname.clone(),
+            identity: Some(identity),
+        })
+    })
+}
+
+fn parallel<T: Sync, U: Send>(items: &[T], f: impl Fn(&T) -> Option<U> + Sync) -> Vec<U> {
+    let next = std::sync::atomic::AtomicUsize::new(0);
+
+    std::thread::scope(|scope| {
+        (0..WORKERS.min(items.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+
+                    while let Some(item) =
+                        items.get(next.fetch_add(1, std::sync::atomic::Ordering::Relaxed))
+                    {
+                        results.extend(f(item));
+                    }
+
+                    results
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_default())
+            .collect()
+    })
+}
+
+fn query() -> Vec<u8> {
+    let mut packet = vec![0, 0, 0, 0, 0, SERVICES.len() as u8, 0, 0, 0, 0, 0, 0];
+
+    for service in SERVICES {
+        for label in service.split('.') {
+            packet.push(label.len() as u8);
+            packet.extend_from_slice(label.as_bytes());
+        }
+        packet.push(0);
+        packet.extend_from_slice(&PTR.to_be_bytes());
+        packet.extend_from_slice(&UNICAST_IN.to_be_bytes());
+    }
+
+    packet
+}
+
+fn parse(packet: &[u8]) -> Option<Answers> {
+    let questions = read_u16(packet, 4)?;
+    let records = (6..12)
+        .step_by(2)
+        .map(|offset| read_u16(packet, offset))
+        .sum::<Option<u16>>()?;
+
+    let mut answers = Answers::default();
+    let mut offset = 12;
+
+    for _ in 0..questions {
+        offset = name(packet, offset)?.1 + 4;
+    }
+
+    for _ in 0..records {
+        let (owner, next) = name(packet, offset)?;
+        let kind = read_u16(packet, next)?;
+        let len = read_u16(packet, next + 8)? as usize;
+        let start = next + 10;
+        let data = packet.get(start..start + len)?;
+
+        match kind {
+            A => {
+                if let [a, b, c, d] = *data {
+                    answers
+                        .hosts
+                        .push((owner, std::net::Ipv4Addr::new(a, b, c, d)));
+                }
+            }
+            PTR => answers.instances.push((owner, name(packet, start)?.0)),
+            SRV => {
+                let port = read_u16(packet, start + 4)?;
+                let target = name(packet, start + 6)?.0;
+
+                answers.services.push((owner, target, port));
+            }
+            _ => (),
+        }
+
+        offset = start + len;
+    }
+
+    Some(answers)
+}
+
+fn name(packet: &[u8], mut offset: usize) -> Option<(String, usize)> {
+    let mut labels = Vec::new();
+    let mut end = None;
+
+    for _ in 0..packet.len() {
+        let len = *packet.get(offset)? as usize;
+
+        if len & 0xc0 == 0xc0 {
+            end.get_or_insert(offset + 2);
+            offset = (len & 0x3f) << 8 | *packet.get(offset + 1)? as usize;
+        } else if len == 0 {
+            return Some((labels.join("."), end.unwrap_or(offset + 1)));
+        } else {
+            let label = packet.get(offset + 1..offset + 1 + len)?;
+            labels.push(String::from_utf8_lossy(label).into_owned());
+            offset += 1 + len;
+        }
+    }
+
+    None
+}
+
+fn read_u16(packet: &[u8], offset: usize) -> Option<u16> {
+    let bytes = packet.get(offset..offset + 2)?;
+
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+#[cfg(test)]
+mod test {
+    fn encode(packet: &mut Vec<u8>, name: &str) {
+        for label in name.split('.') {
+            packet.push(label.len() as u8);
+            packet.extend_from_slice(label.as_bytes());
+        }
+        packet.push(0);
+    }
+
+    fn record(packet: &mut Vec<u8>, owner: &[u8], kind: u16, data: &[u8]) -> u8 {
+        packet.extend_from_slice(owner);
+        packet.extend_from_slice(&kind.to_be_bytes());
+        packet.extend_from_slice(&[0x80, 0x01, 0, 0, 0x11, 0x94]);
+        packet.extend_from_slice(&(data.len() as u16).to_be_bytes());
+
+        let offset = packet.len() as u8;
+        packet.extend_from_slice(data);
+
+        offset
+    }
+
+    fn response() -> Vec<u8> {
+        let mut packet = vec![0, 0, 0x84, 0, 0, 0, 0, 1, 0, 0, 0, 2];
+
+        let service = packet.len() as u8;
+        let mut owner = Vec::new();
+        encode(&mut owner, super::SCPI);
+        let mut instance = b"\x09rp-f01234".to_vec();
+        instance.extend_from_slice(&[0xc0, service]);
+        let instance = record(&mut packet, &owner, super::PTR, &instance);
+
+        let mut target = Vec::new();
+        encode(&mut target, "rp-f01234.local");
+        let mut srv = vec![0, 0, 0, 0, 0x13, 0x88];
+        srv.extend_from_slice(&target);
+        record(&mut packet, &[0xc0, instance], super::SRV, &srv);
+
+        record(&mut packet, &target, super::A, &[192, 168, 1, 42]);
+
+        packet
+    }
+
+    #[test]
+    fn parse() {
+        let answers = super::parse(&response()).unwrap();
+
+        assert_eq!(
+            answers,
+            super::Answers {
+                hosts: vec![(
+                    "rp-f01234.local".to_string(),
+                    std::net::Ipv4Addr::new(192, 168, 1, 42)
+                )],
+                instances: vec![(
+                    super::SCPI.to_string(),
+                    format!("rp-f01234.{}", super::SCPI)
+                )],
+                services: vec![(
+                    format!("rp-f01234.{}", super::SCPI),
+                    "rp-f01234.local".to_string(),
+                    5000
+                )],
+            }
+        );
+    }
+
+    #[test]
+    fn candidates() {
+        let answers = super::parse(&response()).unwrap();
+
+        assert_eq!(
+            answers.candidates(std::net::Ipv4Addr::new(10, 0, 0, 1)),
+            vec![(
+                "192.168.1.42:5000".parse().unwrap(),
+                Some("rp-f01234.local".to_string())
+            )]
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let mut packet = response();
+        packet.truncate(packet.len() - 2);
+        assert_eq!(super::parse(&packet), None);
+
+        let looping = [0, 0, 0x84, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0xc0, 12];
+        assert_eq!(super::parse(&looping), None);
+    }
+
+    #[test]
+    fn scan_invalid() {
+        assert!(super::scan("192.168.1.0").is_err());
+        assert!(super::scan("192.168.1.0/8").is_err());
+        assert!(super::scan("example/24").is_err());
+    }
+}
@@ -0,0 +1,92 @@
+use std::io::Write as _;
+
+pub fn csv(path: &std::path::Path, columns: &[(&str, &[f64])]) -> std::io::Result<()> {
+    let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+
+    let header = columns
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(",");
+    writeln!(file, "{header}")?;
+
+    let rows = columns
+        .iter()
+        .map(|(_, values)| values.len())
+        .max()
+        .unwrap_or_default();
+
+    for row in 0..rows {
+        let line = columns
+            .iter()
+            .map(|(_, values)| match values.get(row) {
+                Some(value) => value.to_string(),
+                None => String::new(),
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        writeln!(file, "{line}")?;
+    }
+
+    file.flush()
+}
+
+pub fn raw(path: &std::path::Path, columns: &[&[f64]]) -> std::io::Result<()> {
+    let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+
+    let rows = columns
+        .iter()
+        .map(|values| values.len())
+        .min()
+        .unwrap_or_default();
+
+    for row in 0..rows {
+        for values in columns {
+            file.write_all(&(values[row] as f32).to_le_bytes())?;
+        }
+    }
+
+    file.flush()
+}
+
+pub fn npy(path: &std::path::Path, columns: &[(&str, &[f64])]) -> std::io::Result<()> {
+    let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+
+    let rows = columns
+        .iter()
+        .map(|(_, values)| values.len())
+        .max()
+        .unwrap_or_default();
+
+    let descr = columns
+        .iter()
+        .map(|(name, _)| format!("('{}', '<f8')", name.replace('\'', "\\'")))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut header =
+        format!("{{'descr': [{descr}], 'fortran_order': False, 'shape': ({rows},), }}");
+
+    let (version, preamble) = if header.is_ascii() { (1, 10) } else { (3, 12) };
+    let padding = 64 - (preamble + header.len() + 1) % 64;
+    header.push_str(&" ".repeat(padding % 64));
+    header.push('\n');
+
+    file.write_all(b"\x93NUMPY")?;
+    file.write_all(&[version, 0])?;
+    if version == 1 {
+        file.write_all(&(header.len() as u16).to_le_bytes())?;
+    } else {
+        file.write_all(&(header.len() as u32).to_le_bytes())?;
+    }
+    file.write_all(header.as_bytes())?;
+
+    for row in 0..rows {
+        for (_, values) in columns {
+            let value = values.get(row).copied().unwrap_or(f64::NAN);
+            file.write_all(&value.to_le_bytes())?;
+        }
+    }
+
+    file.flush()
+}
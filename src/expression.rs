@@ -0,0 +1,308 @@
+#[derive(Clone, Debug)]
+pub enum Expression {
+    Number(f64),
+    Variable(String),
+    Negate(Box<Expression>),
+    Binary(char, Box<Expression>, Box<Expression>),
+    Call(String, Box<Expression>),
+}
+
+impl std::str::FromStr for Expression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            position: 0,
+        };
+
+        let expression = parser.sum()?;
+
+        match parser.peek() {
+            None => Ok(expression),
+            Some(token) => Err(format!("Unexpected '{token}'")),
+        }
+    }
+}
+
+impl Expression {
+    pub fn eval<F>(&self, variable: &F) -> f64
+    where
+        F: Fn(&str) -> Option<f64>,
+    {
+        match self {
+            Self::Number(value) => *value,
+            Self::Variable(name) => variable(name).unwrap_or(f64::NAN),
+            Self::Negate(expression) => -expression.eval(variable),
+            Self::Binary(operator, left, right) => {
+                let (left, right) = (left.eval(variable), right.eval(variable));
+
+                match operator {
+                    '+' => left + right,
+                    '-' => left - right,
+                    '*' => left * right,
+                    '/' => left / right,
+                    '^' => left.powf(right),
+                    _ => f64::NAN,
+                }
+            }
+            Self::Call(function, argument) => {
+                let argument = argument.eval(variable);
+
+                match function.as_str() {
+                    "abs" => argument.abs(),
+                    "cos" => argument.cos(),
+                    "exp" => argument.exp(),
+                    "ln" | "log" => argument.ln(),
+                    "log10" => argument.log10(),
+                    "sin" => argument.sin(),
+                    "sqrt" => argument.sqrt(),
+                    "tan" => argument.tan(),
+                    _ => f64::NAN,
+                }
+            }
+        }
+    }
+
+    pub fn variables(&self) -> Vec<String> {
+        match self {
+            Self::Number(_) => Vec::new(),
+            Self::Variable(name) => vec![name.clone()],
+            Self::Negate(expression) | Self::Call(_, expression) => expression.variables(),
+            Self::Binary(_, left, right) => {
+                let mut variables = left.variables();
+                variables.extend(right.variables());
+                variables
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Identifier(String),
+    Operator(char),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(value) => write!(f, "{value}"),
+            Self::Identifier(name) => write!(f, "{name}"),
+            Self::Operator(operator) => write!(f, "{operator}"),
+        }
+    }
+}
+
+const FUNCTIONS: [&str; 9] = [
+    "abs", "cos", "exp", "ln", "log", "log10", "sin", "sqrt", "tan",
+];
+
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.peek().copied() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut number = String::new();
+
+            while let Some(c) = chars.peek().copied() {
+                let exponent = (c == '-' || c == '+') && number.ends_with(['e', 'E']);
+
+                if c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E' || exponent {
+                    number.push(c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+
+            let value = number
+                .parse()
+                .map_err(|_| format!("Invalid number '{number}'"))?;
+            tokens.push(Token::Number(value));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut identifier = String::new();
+
+            while let Some(c) = chars.peek().copied() {
+                if c.is_alphanumeric() || c == '_' {
+                    identifier.push(c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+
+            tokens.push(Token::Identifier(identifier.to_lowercase()));
+        } else if "+-*/^()".contains(c) {
+            tokens.push(Token::Operator(c));
+            chars.next();
+        } else {
+            return Err(format!("Unexpected '{c}'"));
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn operator(&mut self, operators: &str) -> Option<char> {
+        match self.peek() {
+            Some(Token::Operator(operator)) if operators.contains(*operator) => {
+                let operator = *operator;
+                self.position += 1;
+                Some(operator)
+            }
+            _ => None,
+        }
+    }
+
+    fn sum(&mut self) -> Result<Expression, String> {
+        let mut expression = self.product()?;
+
+        while let Some(operator) = self.operator("+-") {
+            let right = self.product()?;
+            expression = Expression::Binary(operator, Box::new(expression), Box::new(right));
+        }
+
+        Ok(expression)
+    }
+
+    fn product(&mut self) -> Result<Expression, String> {
+        let mut expression = self.unary()?;
+
+        while let Some(operator) = self.operator("*/") {
+            let right = self.unary()?;
+            expression = Expression::Binary(operator, Box::new(expression), Box::new(right));
+        }
+
+        Ok(expression)
+    }
+
+    fn unary(&mut self) -> Result<Expression, String> {
+        match self.operator("+-") {
+            Some('-') => Ok(Expression::Negate(Box::new(self.unary()?))),
+            Some(_) => self.unary(),
+            None => self.power(),
+        }
+    }
+
+    fn power(&mut self) -> Result<Expression, String> {
+        let base = self.atom()?;
+
+        if self.operator("^").is_some() {
+            let exponent = self.unary()?;
+            return Ok(Expression::Binary('^', Box::new(base), Box::new(exponent)));
+        }
+
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Expression, String> {
+        match self.next() {
+            Some(Token::Number(value)) => Ok(Expression::Number(value)),
+            Some(Token::Identifier(name)) => {
+                if FUNCTIONS.contains(&name.as_str()) {
+                    if self.operator("(").is_none() {
+                        return Err(format!("Expected '(' after {name}"));
+                    }
+
+                    let argument = self.sum()?;
+
+                    if self.operator(")").is_none() {
+                        return Err("Expected ')'".to_string());
+                    }
+
+                    Ok(Expression::Call(name, Box::new(argument)))
+                } else if name == "pi" {
+                    Ok(Expression::Number(std::f64::consts::PI))
+                } else {
+                    Ok(Expression::Variable(name))
+                }
+            }
+            Some(Token::Operator('(')) => {
+                let expression = self.sum()?;
+
+                if self.operator(")").is_none() {
+                    return Err("Expected ')'".to_string());
+                }
+
+                Ok(expression)
+            }
+            Some(token) => Err(format!("Unexpected '{token}'")),
+            None => Err("Unexpected end of expression".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Expression;
+
+    fn eval(s: &str) -> f64 {
+        s.parse::<Expression>()
+            .unwrap()
+            .eval(&|name| (name == "x").then_some(2.0))
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(eval("1 + 2 * 3"), 7.0);
+        assert_eq!(eval("(1 + 2) * 3"), 9.0);
+        assert_eq!(eval("-2^2"), -4.0);
+        assert_eq!(eval("2^-1"), 0.5);
+        assert_eq!(eval("2 * x^2"), 8.0);
+    }
+
+    #[test]
+    fn associativity() {
+        assert_eq!(eval("2^3^2"), 512.0);
+        assert_eq!(eval("1-2-3"), -4.0);
+        assert_eq!(eval("8/2/2"), 2.0);
+    }
+
+    #[test]
+    fn call() {
+        assert_eq!(eval("sin(0)"), 0.0);
+        assert_eq!(eval("sqrt(4) + abs(-1)"), 3.0);
+        assert_eq!(eval("exp(ln(x))"), 2.0);
+        assert_eq!(eval("cos(pi)"), -1.0);
+    }
+
+    #[test]
+    fn unknown_identifier() {
+        let expression = "x + foo".parse::<Expression>().unwrap();
+
+        assert_eq!(expression.variables(), ["x", "foo"]);
+        assert!(expression.eval(&|_| None).is_nan());
+    }
+
+    #[test]
+    fn error() {
+        let err = |s: &str| s.parse::<Expression>().unwrap_err();
+
+        assert_eq!(err("sin x"), "Expected '(' after sin");
+        assert_eq!(err("(1+2"), "Expected ')'");
+        assert_eq!(err("1 +"), "Unexpected end of expression");
+        assert_eq!(err("1 2"), "Unexpected '2'");
+        assert_eq!(err("1 $ 2"), "Unexpected '$'");
+    }
+}
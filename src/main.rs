@@ -1,8 +1,19 @@
 #![warn(warnings)]
 
 mod application;
+mod bode;
+mod capture;
 mod color;
+mod connection;
+mod export;
+mod expression;
+mod measure;
+mod persistence;
+mod redpitaya;
 mod scales;
+mod simulator;
+mod spectrum;
+mod waveform;
 mod widget;
 
 use clap::Parser;
@@ -11,8 +22,22 @@ use scales::Scales;
 
 #[derive(Parser)]
 struct Opt {
-    #[clap(default_value = "127.0.0.1:5000")]
-    addr: String,
+    #[clap(help = "Board address, a picker is shown when omitted")]
+    addr: Option<String>,
+    #[clap(
+        long,
+        global = true,
+        help = "Use a simulated board instead of connecting to addr"
+    )]
+    simulate: bool,
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    #[clap(about = "Capture the inputs to a file without launching the interface")]
+    Capture(capture::Opt),
 }
 
 fn main() {
@@ -20,8 +45,33 @@ fn main() {
 
     let opt = Opt::parse();
 
-    let redpitaya = redpitaya_scpi::Redpitaya::new(opt.addr);
+    let addr = if opt.simulate {
+        Some(simulator::spawn().expect("Unable to launch simulator"))
+    } else {
+        opt.addr
+    };
+
+    if let Some(Command::Capture(capture)) = opt.command {
+        let Some(addr) = addr else {
+            eprintln!("An address is required to capture");
+            std::process::exit(1);
+        };
+
+        if let Err(err) = connection::identify(&addr) {
+            eprintln!("Unable to connect to {addr}: {err}");
+            std::process::exit(1);
+        }
+
+        let redpitaya = redpitaya::Redpitaya::new(addr);
+
+        if let Err(err) = capture::run(&redpitaya, &capture) {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+
+        return;
+    }
 
     let app = relm4::RelmApp::new("com.yellow-pitaya.frontend").with_args(Vec::new());
-    app.run::<application::Model>(redpitaya);
+    app.run::<application::connection::Model>(addr);
 }
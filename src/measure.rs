@@ -0,0 +1,294 @@
+pub const NAMES: [(&str, &str); 11] = [
+    ("Vpp", "V"),
+    ("Vmin", "V"),
+    ("Vmax", "V"),
+    ("Mean", "V"),
+    ("RMS", "V"),
+    ("Frequency", "Hz"),
+    ("Period", "s"),
+    ("Duty cycle", "%"),
+    ("Rise time", "s"),
+    ("Fall time", "s"),
+    ("Overshoot", "%"),
+];
+
+#[derive(Clone, Copy, Debug)]
+pub struct Measurements {
+    pub values: [f64; NAMES.len()],
+}
+
+impl Measurements {
+    pub fn min(history: &[Self]) -> Self {
+        Self::reduce(history, |values| {
+            values.iter().copied().fold(f64::NAN, f64::min)
+        })
+    }
+
+    pub fn max(history: &[Self]) -> Self {
+        Self::reduce(history, |values| {
+            values.iter().copied().fold(f64::NAN, f64::max)
+        })
+    }
+
+    pub fn mean(history: &[Self]) -> Self {
+        Self::reduce(history, |values| {
+            values.iter().sum::<f64>() / values.len() as f64
+        })
+    }
+
+    fn reduce<F>(history: &[Self], f: F) -> Self
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let mut values = [f64::NAN; NAMES.len()];
+
+        for (i, value) in values.iter_mut().enumerate() {
+            let column = history
+                .iter()
+                .map(|measurements| measurements.values[i])
+                .filter(|value| !value.is_nan())
+                .collect::<Vec<_>>();
+
+            if !column.is_empty() {
+                *value = f(&column);
+            }
+        }
+
+        Self { values }
+    }
+}
+
+pub fn sample_period(rate: redpitaya_scpi::acquire::SamplingRate) -> f64 {
+    let decimation = redpitaya_scpi::acquire::Decimation::from(rate);
+
+    String::from(decimation).parse::<f64>().unwrap_or(1.0) / 125_000_000.0
+}
+
+pub fn measure(samples: &[f64], period: f64) -> Measurements {
+    let mut values = [f64::NAN; NAMES.len()];
+
+    if samples.is_empty() {
+        return Measurements { values };
+    }
+
+    let min = samples.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = samples.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    let rms = (samples.iter().map(|x| x * x).sum::<f64>() / samples.len() as f64).sqrt();
+
+    values[0] = max - min;
+    values[1] = min;
+    values[2] = max;
+    values[3] = mean;
+    values[4] = rms;
+
+    let (base, top) = levels(samples, min, max);
+    let amplitude = top - base;
+
+    if amplitude <= f64::EPSILON {
+        return Measurements { values };
+    }
+
+    let (rising, falling) = edges(samples, base, top);
+
+    if rising.len() >= 2 {
+        let cycles = (rising.len() - 1) as f64;
+        let samples_per_cycle = (rising[rising.len() - 1] - rising[0]) as f64 / cycles;
+
+        values[5] = 1.0 / (samples_per_cycle * period);
+        values[6] = samples_per_cycle * period;
+
+        let high = rising
+            .windows(2)
+            .filter_map(|cycle| {
+                falling
+                    .iter()
+                    .find(|fall| **fall > cycle[0] && **fall < cycle[1])
+                    .map(|fall| (fall - cycle[0]) as f64 / (cycle[1] - cycle[0]) as f64)
+            })
+            .collect::<Vec<_>>();
+
+        if !high.is_empty() {
+            values[7] = high.iter().sum::<f64>() / high.len() as f64 * 100.0;
+        }
+    }
+
+    let low = base + 0.1 * amplitude;
+    let high = base + 0.9 * amplitude;
+
+    values[8] = transition(samples, &rising, |x| x <= low, |x| x >= high) * period;
+    values[9] = transition(samples, &falling, |x| x >= high, |x| x <= low) * period;
+    values[10] = (max - top) / amplitude * 100.0;
+
+    Measurements { values }
+}
+
+fn levels(samples: &[f64], min: f64, max: f64) -> (f64, f64) {
+    const BINS: usize = 100;
+
+    let width = (max - min) / BINS as f64;
+
+    if width <= 0.0 {
+        return (min, max);
+    }
+
+    let mut histogram = [(0, 0.0); BINS];
+
+    for sample in samples {
+        let bin = (((sample - min) / width) as usize).min(BINS - 1);
+
+        histogram[bin].0 += 1;
+        histogram[bin].1 += sample;
+    }
+
+    let mode = |bins: std::ops::Range<usize>| {
+        let bin = bins.max_by_key(|bin| histogram[*bin].0).unwrap_or_default();
+        let (count, sum) = histogram[bin];
+
+        sum / count.max(1) as f64
+    };
+
+    (mode(0..BINS / 2), mode(BINS / 2..BINS))
+}
+
+fn edges(samples: &[f64], base: f64, top: f64) -> (Vec<usize>, Vec<usize>) {
+    let middle = (base + top) / 2.0;
+    let hysteresis = (top - base) / 10.0;
+
+    let mut rising = Vec::new();
+    let mut falling = Vec::new();
+    let mut state = None;
+
+    for (i, sample) in samples.iter().enumerate() {
+        if *sample > middle + hysteresis && state != Some(true) {
+            if state.is_some() {
+                rising.push(i);
+            }
+            state = Some(true);
+        } else if *sample < middle - hysteresis && state != Some(false) {
+            if state.is_some() {
+                falling.push(i);
+            }
+            state = Some(false);
+        }
+    }
+
+    (rising, falling)
+}
+
+fn transition<S, E>(samples: &[f64], edges: &[usize], start: S, end: E) -> f64
+where
+    S: Fn(f64) -> bool,
+    E: Fn(f64) -> bool,
+{
+    let durations = edges
+        .iter()
+        .filter_map(|edge| {
+            let from = (0..=*edge).rev().find(|i| start(samples[*i]))?;
+            let to = (*edge..samples.len()).find(|i| end(samples[*i]))?;
+
+            Some((to - from) as f64)
+        })
+        .collect::<Vec<_>>();
+
+    if durations.is_empty() {
+        f64::NAN
+    } else {
+        durations.iter().sum::<f64>() / durations.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    const PERIOD: f64 = 1e-6;
+
+    fn trapezoid(duty: usize, ramp: usize) -> Vec<f64> {
+        (0..1_000)
+            .map(|i| {
+                let j = i % 100;
+
+                if j < ramp {
+                    j as f64 / ramp as f64 * 2.0
+                } else if j < duty {
+                    2.0
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+
+    fn assert_close(value: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (value - expected).abs() <= tolerance,
+            "{value} is not within {tolerance} of {expected}"
+        );
+    }
+
+    #[test]
+    fn square() {
+        let values = super::measure(&trapezoid(25, 0), PERIOD).values;
+
+        assert_close(values[0], 2.0, 1e-9);
+        assert_close(values[1], 0.0, 1e-9);
+        assert_close(values[2], 2.0, 1e-9);
+        assert_close(values[3], 0.5, 1e-9);
+        assert_close(values[5], 10_000.0, 1e-6);
+        assert_close(values[6], 100.0 * PERIOD, 1e-12);
+        assert_close(values[7], 25.0, 1e-9);
+        assert_close(values[8], PERIOD, 1e-12);
+        assert_close(values[9], PERIOD, 1e-12);
+        assert_close(values[10], 0.0, 1e-9);
+    }
+
+    #[test]
+    fn rise_time() {
+        let values = super::measure(&trapezoid(50, 10), PERIOD).values;
+
+        assert_close(values[5], 10_000.0, 1e-6);
+        assert_close(values[8], 8.0 * PERIOD, PERIOD);
+        assert_close(values[9], PERIOD, 1e-12);
+    }
+
+    #[test]
+    fn sine() {
+        let samples = (0..1_000)
+            .map(|i| (2.0 * std::f64::consts::PI * i as f64 / 200.0).sin())
+            .collect::<Vec<_>>();
+        let values = super::measure(&samples, PERIOD).values;
+
+        assert_close(values[0], 2.0, 1e-3);
+        assert_close(values[3], 0.0, 1e-9);
+        assert_close(values[4], std::f64::consts::FRAC_1_SQRT_2, 1e-3);
+        assert_close(values[5], 5_000.0, 1e-6);
+        assert_close(values[7], 50.0, 1.0);
+    }
+
+    #[test]
+    fn flat() {
+        let values = super::measure(&[1.0; 100], PERIOD).values;
+
+        assert_close(values[0], 0.0, 1e-9);
+        assert_close(values[3], 1.0, 1e-9);
+        assert!(values[5].is_nan());
+        assert!(values[7].is_nan());
+    }
+
+    #[test]
+    fn statistics() {
+        let mut first = super::Measurements {
+            values: [f64::NAN; super::NAMES.len()],
+        };
+        first.values[0] = 1.0;
+        let mut second = first;
+        second.values[0] = 3.0;
+        second.values[1] = 2.0;
+        let history = [first, second];
+
+        assert_eq!(super::Measurements::min(&history).values[0], 1.0);
+        assert_eq!(super::Measurements::max(&history).values[0], 3.0);
+        assert_eq!(super::Measurements::mean(&history).values[0], 2.0);
+        assert_eq!(super::Measurements::mean(&history).values[1], 2.0);
+        assert!(super::Measurements::mean(&history).values[2].is_nan());
+    }
+}
@@ -0,0 +1,203 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Persistence {
+    #[default]
+    Off,
+    Time(f64),
+    Infinite,
+}
+
+impl Persistence {
+    pub const ALL: [Self; 7] = [
+        Self::Off,
+        Self::Time(0.1),
+        Self::Time(0.5),
+        Self::Time(1.0),
+        Self::Time(5.0),
+        Self::Time(10.0),
+        Self::Infinite,
+    ];
+
+    fn decay(self, elapsed: f64) -> f64 {
+        match self {
+            Self::Off => 0.0,
+            Self::Time(time) => (-elapsed / time).exp(),
+            Self::Infinite => 1.0,
+        }
+    }
+}
+
+impl std::fmt::Display for Persistence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Off => write!(f, "Off"),
+            Self::Time(time) => write!(f, "{}", crate::scales::format(*time, "s")),
+            Self::Infinite => write!(f, "Infinite"),
+        }
+    }
+}
+
+pub struct Grid {
+    pub columns: usize,
+    pub rows: usize,
+    hits: Vec<f64>,
+    last: Option<std::time::Instant>,
+}
+
+impl Grid {
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Self {
+            columns,
+            rows,
+            hits: vec![0.0; columns * rows],
+            last: None,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.hits.fill(0.0);
+        self.last = None;
+    }
+
+    pub fn add(&mut self, samples: &[f64], range: f64, persistence: Persistence) {
+        let now = std::time::Instant::now();
+
+        if let Some(last) = self.last {
+            let decay = persistence.decay((now - last).as_secs_f64());
+
+            for hit in &mut self.hits {
+                *hit *= decay;
+            }
+        }
+        self.last = Some(now);
+
+        if samples.is_empty() || range <= 0.0 {
+            return;
+        }
+
+        let rows = self.rows;
+        let row = |value: f64| {
+            let position = (range - value) / (2.0 * range) * rows as f64;
+
+            (position.max(0.0) as usize).min(rows - 1)
+        };
+
+        let mut previous = None;
+
+        for (sample, value) in samples.iter().enumerate() {
+            let column = sample * self.columns / samples.len();
+            let current = row(*value);
+            let (top, bottom) = previous.map_or((current, current), |previous| {
+                (usize::min(current, previous), usize::max(current, previous))
+            });
+
+            for row in top..=bottom {
+                self.hits[row * self.columns + column] += 1.0;
+            }
+
+            previous = Some(current);
+        }
+    }
+
+    pub fn intensities(&self) -> Vec<f64> {
+        let max = self.hits.iter().copied().fold(0.0, f64::max);
+
+        if max <= 0.0 {
+            return vec![0.0; self.hits.len()];
+        }
+
+        self.hits
+            .iter()
+            .map(|hit| hit.ln_1p() / max.ln_1p())
+            .collect()
+    }
+}
+
+pub fn heat(intensity: f64) -> (f64, f64, f64) {
+    const STOPS: [(f64, f64, f64); 5] = [
+        (0.0, 0.0, 0.5),
+        (0.0, 0.4, 1.0),
+        (0.0, 1.0, 0.3),
+        (1.0, 1.0, 0.0),
+        (1.0, 0.0, 0.0),
+    ];
+
+    let position = intensity.clamp(0.0, 1.0) * (STOPS.len() - 1) as f64;
+    let index = (position as usize).min(STOPS.len() - 2);
+    let t = position - index as f64;
+    let (a, b) = (STOPS[index], STOPS[index + 1]);
+
+    (
+        a.0 + (b.0 - a.0) * t,
+        a.1 + (b.1 - a.1) * t,
+        a.2 + (b.2 - a.2) * t,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::Persistence;
+
+    #[test]
+    fn accumulate() {
+        let mut grid = super::Grid::new(4, 4);
+
+        grid.add(&[0.0; 4], 1.0, Persistence::Infinite);
+        grid.add(&[0.0; 4], 1.0, Persistence::Infinite);
+
+        for (cell, hit) in grid.hits.iter().enumerate() {
+            let expected = if cell / 4 == 2 { 2.0 } else { 0.0 };
+
+            assert_eq!(*hit, expected, "cell {cell}");
+        }
+
+        let intensities = grid.intensities();
+
+        assert_eq!(intensities[8], 1.0);
+        assert_eq!(intensities[0], 0.0);
+    }
+
+    #[test]
+    fn connect() {
+        let mut grid = super::Grid::new(2, 4);
+
+        grid.add(&[1.0, -1.0], 1.0, Persistence::Infinite);
+
+        assert_eq!(grid.hits, [1.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn off() {
+        let mut grid = super::Grid::new(1, 4);
+
+        grid.add(&[1.0], 1.0, Persistence::Off);
+        grid.add(&[-1.0], 1.0, Persistence::Off);
+
+        assert_eq!(grid.hits, [0.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn clear() {
+        let mut grid = super::Grid::new(4, 4);
+
+        grid.add(&[0.5; 4], 1.0, Persistence::Infinite);
+        grid.clear();
+
+        assert!(grid.hits.iter().all(|hit| *hit == 0.0));
+        assert!(grid.intensities().iter().all(|intensity| *intensity == 0.0));
+        assert!(grid.last.is_none());
+    }
+
+    #[test]
+    fn decay() {
+        assert_eq!(Persistence::Off.decay(1.0), 0.0);
+        assert_eq!(Persistence::Infinite.decay(1.0), 1.0);
+        assert!((Persistence::Time(2.0).decay(2.0) - (-1.0_f64).exp()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn heat() {
+        assert_eq!(super::heat(0.0), (0.0, 0.0, 0.5));
+        assert_eq!(super::heat(1.0), (1.0, 0.0, 0.0));
+        assert_eq!(super::heat(2.0), (1.0, 0.0, 0.0));
+    }
+}
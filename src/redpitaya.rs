@@ -0,0 +1,370 @@
+use redpitaya_scpi::acquire::{Decimation, Gain};
+use redpitaya_scpi::generator::{Form, Source, TriggerSource};
+
+#[derive(Clone, Debug)]
+pub struct Redpitaya {
+    pub acquire: Acquire,
+    pub burst: Burst,
+    pub data: Data,
+    pub generator: Generator,
+    pub trigger: Trigger,
+}
+
+impl Redpitaya {
+    pub fn new(addr: String) -> Self {
+        let socket = crate::connection::Socket::new(addr);
+
+        Self {
+            acquire: Acquire {
+                socket: socket.clone(),
+            },
+            burst: Burst {
+                socket: socket.clone(),
+            },
+            data: Data {
+                socket: socket.clone(),
+            },
+            generator: Generator {
+                socket: socket.clone(),
+            },
+            trigger: Trigger { socket },
+        }
+    }
+
+    pub fn socket(&self) -> &crate::connection::Socket {
+        &self.trigger.socket
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Acquire {
+    socket: crate::connection::Socket,
+}
+
+impl Acquire {
+    pub fn start(&self) -> std::io::Result<()> {
+        self.socket.send("ACQ:START")
+    }
+
+    pub fn stop(&self) -> std::io::Result<()> {
+        self.socket.send("ACQ:STOP")
+    }
+
+    pub fn set_decimation(&self, decimation: Decimation) -> std::io::Result<()> {
+        self.socket
+            .send(format!("ACQ:DEC {}", String::from(decimation)))
+    }
+
+    pub fn decimation(&self) -> std::io::Result<Decimation> {
+        parse(self.socket.query("ACQ:DEC?")?)
+    }
+
+    pub fn set_average(&self, enable: bool) -> std::io::Result<()> {
+        self.socket
+            .send(format!("ACQ:AVG {}", if enable { "ON" } else { "OFF" }))
+    }
+
+    pub fn is_average_enabled(&self) -> std::io::Result<bool> {
+        Ok(self.socket.query("ACQ:AVG?")? == "ON")
+    }
+
+    pub fn set_gain(
+        &self,
+        source: redpitaya_scpi::acquire::Source,
+        gain: Gain,
+    ) -> std::io::Result<()> {
+        self.socket.send(format!(
+            "ACQ:{}:GAIN {}",
+            String::from(source),
+            String::from(gain)
+        ))
+    }
+
+    pub fn gain(&self, source: redpitaya_scpi::acquire::Source) -> std::io::Result<Gain> {
+        parse(
+            self.socket
+                .query(format!("ACQ:{}:GAIN?", String::from(source)))?,
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Burst {
+    socket: crate::connection::Socket,
+}
+
+impl Burst {
+    pub fn set_mode(
+        &self,
+        source: redpitaya_scpi::burst::Source,
+        mode: redpitaya_scpi::burst::Mode,
+    ) -> std::io::Result<()> {
+        self.socket.send(format!(
+            "{}:BURS:STAT {}",
+            String::from(source),
+            String::from(mode)
+        ))
+    }
+
+    pub fn set_count(
+        &self,
+        source: redpitaya_scpi::burst::Source,
+        count: u32,
+    ) -> std::io::Result<()> {
+        self.socket
+            .send(format!("{}:BURS:NCYC {count}", String::from(source)))
+    }
+
+    pub fn set_repetitions(
+        &self,
+        source: redpitaya_scpi::burst::Source,
+        repetitions: u32,
+    ) -> std::io::Result<()> {
+        self.socket
+            .send(format!("{}:BURS:NOR {repetitions}", String::from(source)))
+    }
+
+    pub fn set_period(
+        &self,
+        source: redpitaya_scpi::burst::Source,
+        period: u32,
+    ) -> std::io::Result<()> {
+        self.socket
+            .send(format!("{}:BURS:INT:PER {period}", String::from(source)))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Data {
+    socket: crate::connection::Socket,
+}
+
+impl Data {
+    pub fn set_units(&self, unit: redpitaya_scpi::data::Unit) -> std::io::Result<()> {
+        self.socket
+            .send(format!("ACQ:DATA:UNITS {}", String::from(unit)))
+    }
+
+    pub fn buffer_size(&self) -> std::io::Result<u32> {
+        parse(self.socket.query("ACQ:BUF:SIZE?")?)
+    }
+
+    pub fn read_all(&self, source: redpitaya_scpi::acquire::Source) -> std::io::Result<Vec<f64>> {
+        samples(
+            &self
+                .socket
+                .query(format!("ACQ:{}:DATA?", String::from(source)))?,
+        )
+    }
+
+    pub fn read_oldest(
+        &self,
+        source: redpitaya_scpi::acquire::Source,
+        len: u32,
+    ) -> std::io::Result<Vec<f64>> {
+        samples(
+            &self
+                .socket
+                .query(format!("ACQ:{}:DATA:OLD:N? {len}", String::from(source)))?,
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Generator {
+    socket: crate::connection::Socket,
+}
+
+impl Generator {
+    pub fn start(&self, source: Source) -> std::io::Result<()> {
+        self.socket.send(format!("{}:STATE ON", output(source)))
+    }
+
+    pub fn stop(&self, source: Source) -> std::io::Result<()> {
+        self.socket.send(format!("{}:STATE OFF", output(source)))
+    }
+
+    pub fn is_started(&self, source: Source) -> std::io::Result<bool> {
+        Ok(self.socket.query(format!("{}:STATE?", output(source)))? == "ON")
+    }
+
+    pub fn set_frequency(&self, source: Source, frequency: u32) -> std::io::Result<()> {
+        self.socket
+            .send(format!("{}:FREQ:FIX {frequency}", String::from(source)))
+    }
+
+    pub fn frequency(&self, source: Source) -> std::io::Result<u32> {
+        parse::<f32>(
+            self.socket
+                .query(format!("{}:FREQ:FIX?", String::from(source)))?,
+        )
+        .map(|frequency| frequency as u32)
+    }
+
+    pub fn set_form(&self, source: Source, form: Form) -> std::io::Result<()> {
+        self.socket.send(format!(
+            "{}:FUNC {}",
+            String::from(source),
+            String::from(form)
+        ))
+    }
+
+    pub fn form(&self, source: Source) -> std::io::Result<Form> {
+        parse(
+            self.socket
+                .query(format!("{}:FUNC?", String::from(source)))?,
+        )
+    }
+
+    pub fn set_amplitude(&self, source: Source, amplitude: f32) -> std::io::Result<()> {
+        self.socket
+            .send(format!("{}:VOLT {amplitude}", String::from(source)))
+    }
+
+    pub fn amplitude(&self, source: Source) -> std::io::Result<f32> {
+        parse(
+            self.socket
+                .query(format!("{}:VOLT?", String::from(source)))?,
+        )
+    }
+
+    pub fn set_offset(&self, source: Source, offset: f32) -> std::io::Result<()> {
+        self.socket
+            .send(format!("{}:VOLT:OFFS {offset}", String::from(source)))
+    }
+
+    pub fn offset(&self, source: Source) -> std::io::Result<f32> {
+        parse(
+            self.socket
+                .query(format!("{}:VOLT:OFFS?", String::from(source)))?,
+        )
+    }
+
+    pub fn set_phase(&self, source: Source, phase: i32) -> std::io::Result<()> {
+        self.socket
+            .send(format!("{}:PHAS {phase}", String::from(source)))
+    }
+
+    pub fn phase(&self, source: Source) -> std::io::Result<i32> {
+        parse(
+            self.socket
+                .query(format!("{}:PHAS?", String::from(source)))?,
+        )
+    }
+
+    pub fn set_duty_cycle(&self, source: Source, duty_cycle: f32) -> std::io::Result<()> {
+        self.socket
+            .send(format!("{}:DCYC {duty_cycle}", String::from(source)))
+    }
+
+    pub fn duty_cycle(&self, source: Source) -> std::io::Result<f32> {
+        parse(
+            self.socket
+                .query(format!("{}:DCYC?", String::from(source)))?,
+        )
+    }
+
+    pub fn set_arbitrary_waveform(&self, source: Source, data: &[f32]) -> std::io::Result<()> {
+        let data = data
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        self.socket
+            .send(format!("{}:TRAC:DATA:DATA {data}", String::from(source)))
+    }
+
+    pub fn set_trigger_source(
+        &self,
+        source: Source,
+        trigger: TriggerSource,
+    ) -> std::io::Result<()> {
+        self.socket.send(format!(
+            "{}:TRIG:SOUR {}",
+            String::from(source),
+            String::from(trigger)
+        ))
+    }
+
+    pub fn trigger(&self, source: Source) -> std::io::Result<()> {
+        self.socket
+            .send(format!("{}:TRIG:IMM", String::from(source)))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Trigger {
+    socket: crate::connection::Socket,
+}
+
+impl Trigger {
+    pub fn enable(&self, source: redpitaya_scpi::trigger::Source) -> std::io::Result<()> {
+        self.socket
+            .send(format!("ACQ:TRIG {}", String::from(source)))
+    }
+
+    pub fn state(&self) -> std::io::Result<redpitaya_scpi::trigger::State> {
+        parse(self.socket.query("ACQ:TRIG:STAT?")?)
+    }
+
+    pub fn set_delay(&self, delay: u16) -> std::io::Result<()> {
+        self.socket.send(format!("ACQ:TRIG:DLY {delay}"))
+    }
+
+    pub fn set_level(&self, level: f32) -> std::io::Result<()> {
+        self.socket.send(format!("ACQ:TRIG:LEV {level}"))
+    }
+}
+
+fn output(source: Source) -> &'static str {
+    match source {
+        Source::OUT1 => "OUTPUT1",
+        Source::OUT2 => "OUTPUT2",
+    }
+}
+
+fn parse<T>(answer: String) -> std::io::Result<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    answer.parse().map_err(|err| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("invalid answer '{answer}': {err}"),
+        )
+    })
+}
+
+fn samples(answer: &str) -> std::io::Result<Vec<f64>> {
+    answer
+        .trim_matches(|c: char| c == '{' || c == '}' || c == '!' || c.is_alphabetic())
+        .split(',')
+        .map(|sample| parse(sample.trim().to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn samples() {
+        assert_eq!(
+            super::samples("{0.5,-1.25,2}").unwrap(),
+            vec![0.5, -1.25, 2.0]
+        );
+        assert!(super::samples("{0.5,ERR!}").is_err());
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            super::parse::<redpitaya_scpi::generator::Form>("SINE".to_string()).unwrap(),
+            redpitaya_scpi::generator::Form::SINE
+        );
+        assert_eq!(
+            super::parse::<u32>("abc".to_string()).unwrap_err().kind(),
+            std::io::ErrorKind::InvalidData
+        );
+    }
+}
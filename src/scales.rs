@@ -8,6 +8,7 @@ pub struct Rect {
 pub struct Scales {
     pub h: (f64, f64),
     pub v: (f64, f64),
+    pub duration: f64,
     pub n_samples: u32,
     pub window: Rect,
 }
@@ -23,11 +24,49 @@ impl Scales {
 
     pub fn with_sampling_rate(&mut self, rate: redpitaya_scpi::acquire::SamplingRate) {
         let duration = rate.buffer_duration();
-        let h =
-            (duration.as_secs() * 1_000_000 + duration.subsec_nanos() as u64 / 1_000) as f64 / 2.0;
+        self.duration =
+            (duration.as_secs() * 1_000_000 + duration.subsec_nanos() as u64 / 1_000) as f64;
 
-        self.h.0 = -h;
-        self.h.1 = h;
+        self.h.0 = -self.duration / 2.0;
+        self.h.1 = self.duration / 2.0;
+    }
+
+    pub fn set_v_div(&mut self, div: f64) {
+        self.zoom_v(div, (self.v.0 + self.v.1) / 2.0);
+    }
+
+    pub fn set_h_div(&mut self, div: f64) {
+        self.zoom_h(div, (self.h.0 + self.h.1) / 2.0);
+    }
+
+    pub fn zoom_v(&mut self, div: f64, center: f64) {
+        let ratio = div / self.v_div();
+
+        self.v.0 = center - (center - self.v.0) * ratio;
+        self.v.1 = center + (self.v.1 - center) * ratio;
+    }
+
+    pub fn zoom_h(&mut self, div: f64, center: f64) {
+        let ratio = div / self.h_div();
+
+        self.h.0 = center - (center - self.h.0) * ratio;
+        self.h.1 = center + (self.h.1 - center) * ratio;
+    }
+
+    pub fn zoom_rect(&mut self, (x0, y0): (i32, i32), (x1, y1): (i32, i32)) {
+        let h = (self.x_to_offset(x0.min(x1)), self.x_to_offset(x0.max(x1)));
+        let v = (self.y_to_offset(y0.max(y1)), self.y_to_offset(y0.min(y1)));
+
+        self.h = h;
+        self.v = v;
+    }
+
+    pub fn pan(&mut self, dx: i32, dy: i32) {
+        let dh = dx as f64 / self.window.width as f64 * self.width();
+        let dv = dy as f64 / self.window.height as f64 * self.height();
+
+        self.h = (self.h.0 - dh, self.h.1 - dh);
+        self.v = (self.v.0 + dv, self.v.1 + dv);
     }
 
     pub fn v_div(&self) -> f64 {
@@ -39,7 +78,11 @@ impl Scales {
     }
 
     pub fn sample_to_ms(&self, sample: u32) -> f64 {
-        sample as f64 / self.n_samples as f64 * (self.h.1 - self.h.0) + self.h.0
+        sample as f64 / self.n_samples as f64 * self.duration - self.duration / 2.0
+    }
+
+    pub fn ms_to_sample(&self, offset: f64) -> f64 {
+        (offset + self.duration / 2.0) / self.duration * self.n_samples as f64
     }
 
     pub fn x_to_offset(&self, x: i32) -> f64 {
@@ -49,4 +92,81 @@ impl Scales {
     pub fn y_to_offset(&self, y: i32) -> f64 {
         y as f64 / -self.window.height as f64 * self.height() + self.v.1
     }
+
+    pub fn offset_to_x(&self, offset: f64) -> i32 {
+        ((offset - self.h.0) / self.width() * self.window.width as f64) as i32
+    }
+
+    pub fn offset_to_y(&self, offset: f64) -> i32 {
+        ((self.v.1 - offset) / self.height() * self.window.height as f64) as i32
+    }
+
+    pub fn duration_to_samples(&self, duration: f64) -> f64 {
+        duration / self.duration * self.n_samples as f64
+    }
+
+    pub fn samples_to_duration(&self, samples: f64) -> f64 {
+        samples / self.n_samples as f64 * self.duration
+    }
+}
+
+pub const V_DIV: (f64, f64) = (0.001, 100.0);
+pub const H_DIV: (f64, f64) = (0.01, 1_000_000.0);
+
+pub fn steps((min, max): (f64, f64)) -> Vec<f64> {
+    let mut steps = Vec::new();
+    let mut decade = 10_f64.powf(min.log10().floor());
+
+    while decade <= max {
+        for mantissa in [1.0, 2.0, 5.0] {
+            let step = mantissa * decade;
+
+            if step >= min * 0.999 && step <= max * 1.001 {
+                steps.push(step);
+            }
+        }
+
+        decade *= 10.0;
+    }
+
+    steps
+}
+
+pub fn next_step(range: (f64, f64), current: f64, zoom_in: bool) -> f64 {
+    let steps = steps(range);
+
+    if zoom_in {
+        steps
+            .iter()
+            .rev()
+            .find(|step| **step < current * 0.999)
+            .copied()
+            .unwrap_or(range.0)
+    } else {
+        steps
+            .iter()
+            .find(|step| **step > current * 1.001)
+            .copied()
+            .unwrap_or(range.1)
+    }
+}
+
+pub fn format(value: f64, unit: &str) -> String {
+    let prefixes = [
+        (1e6, "M"),
+        (1e3, "k"),
+        (1.0, ""),
+        (1e-3, "m"),
+        (1e-6, "µ"),
+        (1e-9, "n"),
+    ];
+
+    for (factor, prefix) in prefixes {
+        if value.abs() >= factor * 0.999 {
+            let value = value / factor;
+            return format!("{} {prefix}{unit}", (value * 1000.0).round() / 1000.0);
+        }
+    }
+
+    format!("0 {unit}")
 }
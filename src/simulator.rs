@@ -0,0 +1,371 @@
+use std::io::BufRead as _;
+use std::io::Write as _;
+
+const BUFFER_SIZE: usize = 16_384;
+const BASE_RATE: f64 = 125_000_000.0;
+const NOISE: f64 = 0.002;
+
+pub fn spawn() -> std::io::Result<String> {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?.to_string();
+
+    log::info!("Simulated redpitaya listening on {addr}");
+
+    std::thread::spawn(move || {
+        let mut state = State::new();
+
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    if let Err(err) = state.serve(stream) {
+                        log::error!("Simulator: {err}");
+                    }
+                }
+                Err(err) => log::error!("Simulator: {err}"),
+            }
+        }
+    });
+
+    Ok(addr)
+}
+
+struct Snapshot {
+    at: std::time::Instant,
+    triggered: bool,
+    data: [Vec<f64>; 2],
+}
+
+struct State {
+    settings: std::collections::HashMap<String, String>,
+    snapshot: Option<Snapshot>,
+    start: std::time::Instant,
+    seed: u64,
+}
+
+impl State {
+    fn new() -> Self {
+        Self {
+            settings: std::collections::HashMap::new(),
+            snapshot: None,
+            start: std::time::Instant::now(),
+            seed: 0x2545_f491_4f6c_dd1d,
+        }
+    }
+
+    fn serve(&mut self, stream: std::net::TcpStream) -> std::io::Result<()> {
+        let mut reader = std::io::BufReader::new(stream.try_clone()?);
+        let mut message = String::new();
+
+        reader.read_line(&mut message)?;
+
+        if let Some(response) = self.handle(message.trim_end()) {
+            let mut stream = stream;
+            stream.write_all(format!("{response}\r\n").as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    fn handle(&mut self, message: &str) -> Option<String> {
+        let (command, args) = match message.split_once(' ') {
+            Some((command, args)) => (command, args.trim()),
+            None => (message, ""),
+        };
+
+        if let Some(command) = command.strip_suffix('?') {
+            return Some(self.query(command, args));
+        }
+
+        match command {
+            "ACQ:START" | "ACQ:STOP" => self.snapshot = None,
+            "ACQ:RST" => {
+                self.settings.retain(|key, _| !key.starts_with("ACQ:"));
+                self.snapshot = None;
+            }
+            "GEN:RST" => self
+                .settings
+                .retain(|key, _| !key.starts_with("SOUR") && !key.starts_with("OUTPUT")),
+            "ACQ:TRIG" => {
+                self.settings.insert(command.to_string(), args.to_string());
+                self.snapshot = None;
+            }
+            "OUTPUT:STATE" => {
+                self.settings
+                    .insert("OUTPUT1:STATE".to_string(), args.to_string());
+                self.settings
+                    .insert("OUTPUT2:STATE".to_string(), args.to_string());
+            }
+            _ => {
+                self.settings.insert(command.to_string(), args.to_string());
+            }
+        }
+
+        None
+    }
+
+    fn query(&mut self, command: &str, args: &str) -> String {
+        match command {
+            "*IDN" => "REDPITAYA,INSTR2014,0,SIMULATOR".to_string(),
+            "ACQ:SRAT" => format!("{} Hz", self.sampling_rate() as u64),
+            "ACQ:TRIG:STAT" => match self.get("ACQ:TRIG").as_str() {
+                "DISABLED" | "NOW" => "TD".to_string(),
+                _ if self.acquire().triggered => "TD".to_string(),
+                _ => "WAIT".to_string(),
+            },
+            "ACQ:WPOS" => {
+                let samples = self.start.elapsed().as_secs_f64() * self.sampling_rate();
+
+                (samples as u64 % BUFFER_SIZE as u64).to_string()
+            }
+            "ACQ:TPOS" => (BUFFER_SIZE / 2).to_string(),
+            _ => match command.strip_prefix("ACQ:SOUR") {
+                Some(data) if data.contains(":DATA") => self.data(data, args),
+                _ => self.get(command),
+            },
+        }
+    }
+
+    fn get(&self, key: &str) -> String {
+        if let Some(value) = self.settings.get(key) {
+            return value.clone();
+        }
+
+        let default = match key {
+            "ACQ:DEC" => "1",
+            "ACQ:AVG" => "OFF",
+            "ACQ:BUF:SIZE" => "16384",
+            "ACQ:DATA:UNITS" => "VOLTS",
+            "ACQ:TRIG" => "DISABLED",
+            "ACQ:TRIG:DLY" | "ACQ:TRIG:DLY:NS" | "ACQ:TRIG:LEV" => "0",
+            "ACQ:TRIG:HYST" => "0.005",
+            _ if key.ends_with(":GAIN") => "LV",
+            _ if key.ends_with(":STATE") => "OFF",
+            _ if key.ends_with(":FUNC") => "SINE",
+            _ if key.ends_with(":FREQ:FIX") => "1000",
+            _ if key.ends_with(":VOLT") => "1",
+            _ if key.ends_with(":VOLT:OFFS") || key.ends_with(":PHAS") => "0",
+            _ if key.ends_with(":DCYC") => "0.5",
+            _ if key.ends_with(":TRIG:SOUR") => "INT",
+            _ if key.ends_with(":BURS:STAT") => "CONTINUOUS",
+            _ if key.ends_with(":BURS:NCYC") || key.ends_with(":BURS:NOR") => "1",
+            _ if key.ends_with(":BURS:INT:PER") => "1000",
+            _ => "0",
+        };
+
+        default.to_string()
+    }
+
+    fn get_f64(&self, key: &str) -> f64 {
+        self.get(key)
+            .trim_end_matches(|c: char| c.is_alphabetic())
+            .parse()
+            .unwrap_or_default()
+    }
+
+    fn sampling_rate(&self) -> f64 {
+        BASE_RATE / self.get_f64("ACQ:DEC").max(1.0)
+    }
+
+    fn data(&mut self, query: &str, args: &str) -> String {
+        let (channel, kind) = query.split_once(":DATA").unwrap_or((query, ""));
+        let channel = match channel {
+            "2" => 1,
+            _ => 0,
+        };
+        let args = args
+            .split(',')
+            .filter_map(|arg| arg.trim().parse::<usize>().ok())
+            .collect::<Vec<_>>();
+
+        let buffer = &self.acquire().data[channel];
+
+        let samples = match (kind, args.as_slice()) {
+            (":STA:END", [start, end]) => {
+                (*start..=*end).map(|i| buffer[i % BUFFER_SIZE]).collect()
+            }
+            (":STA:N", [start, len]) => (*start..start + len)
+                .map(|i| buffer[i % BUFFER_SIZE])
+                .collect(),
+            (":OLD:N", [len]) => buffer[..(*len).min(BUFFER_SIZE)].to_vec(),
+            (":LAT:N", [len]) => buffer[BUFFER_SIZE - (*len).min(BUFFER_SIZE)..].to_vec(),
+            _ => buffer.clone(),
+        };
+
+        let raw = self.get("ACQ:DATA:UNITS") == "RAW";
+        let range = self.range(channel);
+
+        let samples = samples
+            .iter()
+            .map(|sample| {
+                if raw {
+                    format!("{}", (sample / range * 8_192.0).round() as i32)
+                } else {
+                    format!("{sample:.5}")
+                }
+            })
+            .collect::<Vec<_>>();
+
+        format!("{{{}}}", samples.join(","))
+    }
+
+    fn range(&self, channel: usize) -> f64 {
+        match self.get(&format!("ACQ:SOUR{}:GAIN", channel + 1)).as_str() {
+            "HV" => 20.0,
+            _ => 1.0,
+        }
+    }
+
+    fn acquire(&mut self) -> &Snapshot {
+        let expired = match &self.snapshot {
+            Some(snapshot) => snapshot.at.elapsed() > std::time::Duration::from_millis(20),
+            None => true,
+        };
+
+        if expired {
+            self.snapshot = Some(self.snapshot());
+        }
+
+        self.snapshot.as_ref().unwrap()
+    }
+
+    fn snapshot(&mut self) -> Snapshot {
+        let rate = self.sampling_rate();
+        let now = self.start.elapsed().as_secs_f64();
+
+        let signals = [
+            self.signal(0, now, rate, 2 * BUFFER_SIZE),
+            self.signal(1, now, rate, 2 * BUFFER_SIZE),
+        ];
+
+        let delay = self.get_f64("ACQ:TRIG:DLY") as usize;
+        let pre = (BUFFER_SIZE / 2 + delay).min(BUFFER_SIZE);
+
+        let position = self.trigger_position(&signals, pre);
+        let start = position.map(|position| position - pre).unwrap_or(0);
+
+        let mut data = [Vec::new(), Vec::new()];
+
+        for (channel, signal) in signals.iter().enumerate() {
+            let range = self.range(channel);
+            let noise = if self.get("ACQ:AVG") == "ON" {
+                NOISE / self.get_f64("ACQ:DEC").max(1.0).sqrt()
+            } else {
+                NOISE
+            };
+
+            data[channel] = signal[start..start + BUFFER_SIZE]
+                .iter()
+                .map(|sample| (sample + noise * self.gaussian()).clamp(-range, range))
+                .collect();
+        }
+
+        Snapshot {
+            at: std::time::Instant::now(),
+            triggered: position.is_some(),
+            data,
+        }
+    }
+
+    fn trigger_position(&self, signals: &[Vec<f64>; 2], pre: usize) -> Option<usize> {
+        let (channel, rising) = match self.get("ACQ:TRIG").as_str() {
+            "CH1_PE" => (0, true),
+            "CH1_NE" => (0, false),
+            "CH2_PE" => (1, true),
+            "CH2_NE" => (1, false),
+            "EXT_PE" | "EXT_NE" | "AWG_PE" | "AWG_NE" => return Some(pre),
+            _ => return None,
+        };
+
+        let level = self.get_f64("ACQ:TRIG:LEV");
+        let signal = &signals[channel];
+
+        (pre.max(1)..pre + BUFFER_SIZE).find(|&i| {
+            let (previous, current) = (signal[i - 1], signal[i]);
+
+            if rising {
+                previous < level && current >= level
+            } else {
+                previous > level && current <= level
+            }
+        })
+    }
+
+    fn signal(&self, channel: usize, start: f64, rate: f64, len: usize) -> Vec<f64> {
+        let prefix = format!("SOUR{}", channel + 1);
+
+        if self.get(&format!("OUTPUT{}:STATE", channel + 1)) != "ON" {
+            return vec![0.0; len];
+        }
+
+        let form = self.get(&format!("{prefix}:FUNC"));
+        let frequency = self.get_f64(&format!("{prefix}:FREQ:FIX"));
+        let amplitude = self.get_f64(&format!("{prefix}:VOLT"));
+        let offset = self.get_f64(&format!("{prefix}:VOLT:OFFS"));
+        let phase = self.get_f64(&format!("{prefix}:PHAS")) / 360.0;
+        let duty_cycle = self.get_f64(&format!("{prefix}:DCYC"));
+        let arbitrary = self
+            .get(&format!("{prefix}:TRAC:DATA:DATA"))
+            .trim_matches(|c| c == '{' || c == '}')
+            .split(',')
+            .filter_map(|x| x.trim().parse::<f64>().ok())
+            .collect::<Vec<_>>();
+
+        let burst = self.get(&format!("{prefix}:BURS:STAT")) == "BURST";
+        let cycles = self.get_f64(&format!("{prefix}:BURS:NCYC")).max(1.0);
+        let period = self.get_f64(&format!("{prefix}:BURS:INT:PER")) / 1e6;
+
+        (0..len)
+            .map(|i| {
+                let t = start + i as f64 / rate;
+
+                if burst && period > 0.0 && (t % period) * frequency >= cycles {
+                    return offset;
+                }
+
+                let x = (t * frequency + phase).rem_euclid(1.0);
+
+                let y = match form.as_str() {
+                    "SQUARE" => {
+                        if x < 0.5 {
+                            1.0
+                        } else {
+                            -1.0
+                        }
+                    }
+                    "TRIANGLE" => 1.0 - 4.0 * (x - 0.5).abs(),
+                    "SAWU" => 2.0 * x - 1.0,
+                    "SAWD" => 1.0 - 2.0 * x,
+                    "PWM" => {
+                        if x < duty_cycle {
+                            1.0
+                        } else {
+                            -1.0
+                        }
+                    }
+                    "DC" => 1.0,
+                    "ARBITRARY" if !arbitrary.is_empty() => {
+                        arbitrary[(x * arbitrary.len() as f64) as usize % arbitrary.len()]
+                    }
+                    _ => (x * 2.0 * std::f64::consts::PI).sin(),
+                };
+
+                amplitude * y + offset
+            })
+            .collect()
+    }
+
+    fn gaussian(&mut self) -> f64 {
+        let u1 = self.random().max(f64::MIN_POSITIVE);
+        let u2 = self.random();
+
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+
+    fn random(&mut self) -> f64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+
+        (self.seed >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
@@ -0,0 +1,228 @@
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Window {
+    #[default]
+    Rectangular,
+    Hann,
+    Hamming,
+    BlackmanHarris,
+    FlatTop,
+}
+
+impl Window {
+    pub const ALL: [Self; 5] = [
+        Self::Rectangular,
+        Self::Hann,
+        Self::Hamming,
+        Self::BlackmanHarris,
+        Self::FlatTop,
+    ];
+
+    fn coefficients(self, len: usize) -> Vec<f64> {
+        let cosines: &[f64] = match self {
+            Self::Rectangular => &[1.0],
+            Self::Hann => &[0.5, 0.5],
+            Self::Hamming => &[0.54, 0.46],
+            Self::BlackmanHarris => &[0.35875, 0.48829, 0.14128, 0.01168],
+            Self::FlatTop => &[
+                0.215_578_95,
+                0.416_631_58,
+                0.277_263_158,
+                0.083_578_947,
+                0.006_947_368,
+            ],
+        };
+
+        (0..len)
+            .map(|i| {
+                let x = 2.0 * std::f64::consts::PI * i as f64 / len as f64;
+
+                cosines
+                    .iter()
+                    .enumerate()
+                    .map(|(k, a)| {
+                        let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+
+                        sign * a * (k as f64 * x).cos()
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Window {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let display = match self {
+            Self::Rectangular => "Rectangular",
+            Self::Hann => "Hann",
+            Self::Hamming => "Hamming",
+            Self::BlackmanHarris => "Blackman-Harris",
+            Self::FlatTop => "Flat-top",
+        };
+
+        write!(f, "{display}")
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Scaling {
+    #[default]
+    DbV,
+    Linear,
+}
+
+impl Scaling {
+    pub const ALL: [Self; 2] = [Self::DbV, Self::Linear];
+}
+
+impl std::fmt::Display for Scaling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let display = match self {
+            Self::DbV => "dBV",
+            Self::Linear => "Linear",
+        };
+
+        write!(f, "{display}")
+    }
+}
+
+pub struct Spectrum {
+    pub resolution: f64,
+    pub amplitudes: Vec<f64>,
+}
+
+impl Spectrum {
+    pub fn new(samples: &[f64], period: f64, window: Window) -> Self {
+        let len = samples.len().next_power_of_two();
+        let coefficients = window.coefficients(samples.len());
+        let gain = coefficients.iter().sum::<f64>();
+
+        let mut re = vec![0.0; len];
+        let mut im = vec![0.0; len];
+
+        for (i, (sample, coefficient)) in samples.iter().zip(&coefficients).enumerate() {
+            re[i] = sample * coefficient;
+        }
+
+        fft(&mut re, &mut im);
+
+        let amplitudes = (0..=len / 2)
+            .map(|k| {
+                let magnitude = (re[k] * re[k] + im[k] * im[k]).sqrt() / gain;
+
+                if k == 0 {
+                    magnitude
+                } else {
+                    2.0 * magnitude / std::f64::consts::SQRT_2
+                }
+            })
+            .collect();
+
+        Self {
+            resolution: 1.0 / (len as f64 * period),
+            amplitudes,
+        }
+    }
+
+    pub fn frequency(&self, bin: usize) -> f64 {
+        bin as f64 * self.resolution
+    }
+
+    pub fn peaks(&self, count: usize) -> Vec<usize> {
+        let mut peaks = (1..self.amplitudes.len().saturating_sub(1))
+            .filter(|k| {
+                self.amplitudes[*k] > self.amplitudes[k - 1]
+                    && self.amplitudes[*k] >= self.amplitudes[k + 1]
+            })
+            .collect::<Vec<_>>();
+
+        peaks.sort_by(|a, b| self.amplitudes[*b].total_cmp(&self.amplitudes[*a]));
+        peaks.truncate(count);
+
+        peaks
+    }
+}
+
+pub fn db(amplitude: f64) -> f64 {
+    20.0 * amplitude.max(1e-12).log10()
+}
+
+fn fft(re: &mut [f64], im: &mut [f64]) {
+    let len = re.len();
+    let mut j = 0;
+
+    for i in 1..len {
+        let mut bit = len >> 1;
+
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut size = 2;
+
+    while size <= len {
+        let angle = -2.0 * std::f64::consts::PI / size as f64;
+
+        for start in (0..len).step_by(size) {
+            for k in 0..size / 2 {
+                let (sin, cos) = (angle * k as f64).sin_cos();
+                let (a, b) = (start + k, start + k + size / 2);
+
+                let tre = re[b] * cos - im[b] * sin;
+                let tim = re[b] * sin + im[b] * cos;
+
+                re[b] = re[a] - tre;
+                im[b] = im[a] - tim;
+                re[a] += tre;
+                im[a] += tim;
+            }
+        }
+
+        size <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    fn sine(amplitude: f64, frequency: f64, period: f64, offset: f64) -> Vec<f64> {
+        (0..1_024)
+            .map(|i| {
+                offset
+                    + amplitude * (2.0 * std::f64::consts::PI * frequency * i as f64 * period).sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sine_bin() {
+        let period = 1e-3;
+        let samples = sine(2.0, 62.5, period, 0.5);
+        let spectrum = super::Spectrum::new(&samples, period, super::Window::Rectangular);
+
+        assert_eq!(spectrum.amplitudes.len(), 513);
+        assert_eq!(spectrum.peaks(1), [64]);
+        assert!((spectrum.frequency(64) - 62.5).abs() < 1e-9);
+        assert!((spectrum.amplitudes[64] - std::f64::consts::SQRT_2).abs() < 1e-9);
+        assert!((spectrum.amplitudes[0] - 0.5).abs() < 1e-9);
+        assert!(spectrum.amplitudes[100] < 1e-9);
+    }
+
+    #[test]
+    fn flat_top_between_bins() {
+        let period = 1e-3;
+        let samples = sine(1.0, 63.0, period, 0.0);
+        let spectrum = super::Spectrum::new(&samples, period, super::Window::FlatTop);
+        let peak = spectrum.peaks(1)[0];
+
+        assert!(peak == 64 || peak == 65);
+        assert!((spectrum.amplitudes[peak] - std::f64::consts::FRAC_1_SQRT_2).abs() < 0.01);
+    }
+}
@@ -0,0 +1,518 @@
+pub const LEN: usize = 16_384;
+
+pub fn load(path: &std::path::Path) -> std::io::Result<Vec<f64>> {
+    let samples = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("wav") => wav(&std::fs::read(path)?)?,
+        Some("npy") => npy(&std::fs::read(path)?)?,
+        _ => csv(&std::fs::read_to_string(path)?)?,
+    };
+
+    if samples.is_empty() {
+        return Err(std::io::Error::other("no samples"));
+    }
+
+    Ok(normalize(&resample(&samples)))
+}
+
+pub fn expression(expression: &crate::expression::Expression) -> Vec<f64> {
+    let samples = (0..LEN)
+        .map(|i| {
+            let x = i as f64 / LEN as f64;
+
+            expression.eval(&|variable| (variable == "x").then_some(x))
+        })
+        .map(|value| if value.is_finite() { value } else { 0.0 })
+        .collect::<Vec<_>>();
+
+    normalize(&samples)
+}
+
+pub fn resample(samples: &[f64]) -> Vec<f64> {
+    if samples.len() < 2 {
+        return vec![samples.first().copied().unwrap_or_default(); LEN];
+    }
+
+    (0..LEN)
+        .map(|i| {
+            let position = i as f64 * (samples.len() - 1) as f64 / (LEN - 1) as f64;
+            let index = (position as usize).min(samples.len() - 2);
+            let t = position - index as f64;
+
+            samples[index] * (1.0 - t) + samples[index + 1] * t
+        })
+        .collect()
+}
+
+fn normalize(samples: &[f64]) -> Vec<f64> {
+    let peak = samples
+        .iter()
+        .fold(0.0, |peak, sample| sample.abs().max(peak));
+
+    if peak > 1.0 {
+        samples.iter().map(|sample| sample / peak).collect()
+    } else {
+        samples.to_vec()
+    }
+}
+
+fn csv(contents: &str) -> std::io::Result<Vec<f64>> {
+    let samples = contents
+        .lines()
+        .filter_map(|line| {
+            let columns = line
+                .split([',', ';', '\t'])
+                .map(|column| column.trim().parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .ok()?;
+
+            match columns.as_slice() {
+                [value] | [_, value, ..] => Some(*value),
+                [] => None,
+            }
+        })
+        .collect();
+
+    Ok(samples)
+}
+
+fn wav(bytes: &[u8]) -> std::io::Result<Vec<f64>> {
+    let invalid = |message: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(invalid("not a WAV file"));
+    }
+
+    let mut format = None;
+    let mut position = 12;
+
+    while position + 8 <= bytes.len() {
+        let id = &bytes[position..position + 4];
+        let size =
+            u32::from_le_bytes(bytes[position + 4..position + 8].try_into().unwrap()) as usize;
+        let chunk = &bytes[position + 8..(position + 8 + size).min(bytes.len())];
+
+        if id == b"fmt " && chunk.len() >= 16 {
+            let tag = u16::from_le_bytes([chunk[0], chunk[1]]);
+            let channels = u16::from_le_bytes([chunk[2], chunk[3]]) as usize;
+            let bits = u16::from_le_bytes([chunk[14], chunk[15]]) as usize;
+
+            format = Some((tag, channels.max(1), bits));
+        } else if id == b"data" {
+            let (tag, channels, bits) = format.ok_or_else(|| invalid("missing fmt chunk"))?;
+            let width = bits / 8;
+
+            let decode = |frame: &[u8]| -> Option<f64> {
+                let value = match (tag, bits) {
+                    (1, 8) => (frame[0] as f64 - 128.0) / 128.0,
+                    (1, 16) => i16::from_le_bytes([frame[0], frame[1]]) as f64 / 32_768.0,
+                    (1, 24) => {
+                        i32::from_le_bytes([0, frame[0], frame[1], frame[2]]) as f64
+                            / 2_147_483_648.0
+                    }
+                    (1, 32) => {
+                        i32::from_le_bytes(frame[..4].try_into().ok()?) as f64 / 2_147_483_648.0
+                    }
+                    (3, 32) => f32::from_le_bytes(frame[..4].try_into().ok()?) as f64,
+                    (3, 64) => f64::from_le_bytes(frame[..8].try_into().ok()?),
+                    _ => return None,
+                };
+
+                Some(value)
+            };
+
+            if width == 0 {
+                return Err(invalid("unsupported WAV format"));
+            }
+
+            return chunk
+                .chunks_exact(width * channels)
+                .map(|frame| decode(frame).ok_or_else(|| invalid("unsupported WAV format")))
+                .collect();
+        }
+
+        position += 8 + size + size % 2;
+    }
+
+    Err(invalid("missing data chunk"))
+}
+
+fn npy(bytes: &[u8]) -> std::io::Result<Vec<f64>> {
+    let invalid = |message: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+
+    if bytes.len() < 10 || &bytes[0..6] != b"\x93NUMPY" {
+        return Err(invalid("not a NumPy file"));
+    }
+
+    let (length, start) = match (bytes[6], bytes.get(8..12)) {
+        (1, _) => (u16::from_le_bytes([bytes[8], bytes[9]]) as usize, 10),
+        (2 | 3, Some(&[a, b, c, d])) => (u32::from_le_bytes([a, b, c, d]) as usize, 12),
+        _ => return Err(invalid("unsupported NumPy version")),
+    };
+
+    let header = bytes
+        .get(start..start + length)
+        .and_then(|header| std::str::from_utf8(header).ok())
+        .ok_or_else(|| invalid("invalid header"))?;
+    let data = &bytes[start + length..];
+
+    let Literal::Dict(header) = Literal::parse(header).map_err(|err| invalid(&err))? else {
+        return Err(invalid("invalid header"));
+    };
+    let field = |name: &str| {
+        header
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+            .ok_or_else(|| invalid(&format!("missing {name}")))
+    };
+
+    if field("fortran_order")? != &Literal::Bool(false) {
+        return Err(invalid("Fortran order is not supported"));
+    }
+
+    let shape = match field("shape")? {
+        Literal::List(shape) => shape
+            .iter()
+            .map(|dimension| match dimension {
+                Literal::Int(dimension) => Ok(*dimension),
+                _ => Err(invalid("invalid shape")),
+            })
+            .collect::<std::io::Result<Vec<_>>>()?,
+        _ => return Err(invalid("invalid shape")),
+    };
+
+    let (types, column) = match field("descr")? {
+        Literal::Str(kind) => (vec![Dtype::parse(kind)?], 0),
+        Literal::List(fields) => {
+            let fields = fields
+                .iter()
+                .map(|field| match field {
+                    Literal::List(field) => match field.as_slice() {
+                        [Literal::Str(name), Literal::Str(kind)] => {
+                            Ok((name.as_str(), Dtype::parse(kind)?))
+                        }
+                        _ => Err(invalid("unsupported structured dtype")),
+                    },
+                    _ => Err(invalid("invalid descr")),
+                })
+                .collect::<std::io::Result<Vec<_>>>()?;
+            let column = fields
+                .iter()
+                .position(|(name, _)| !name.starts_with("time"))
+                .unwrap_or_default();
+
+            (fields.into_iter().map(|(_, kind)| kind).collect(), column)
+        }
+        _ => return Err(invalid("invalid descr")),
+    };
+
+    let rows = match shape.as_slice() {
+        [rows] | [rows, 1] => *rows,
+        _ => return Err(invalid("only 1-D arrays are supported")),
+    };
+
+    let stride = types.iter().map(|kind| kind.size).sum::<usize>();
+    let offset = types[..column].iter().map(|kind| kind.size).sum::<usize>();
+    let kind = types.get(column).ok_or_else(|| invalid("missing dtype"))?;
+
+    if data.len() < rows * stride {
+        return Err(invalid("truncated data"));
+    }
+
+    data.chunks_exact(stride)
+        .take(rows)
+        .map(|row| {
+            kind.decode(&row[offset..offset + kind.size])
+                .ok_or_else(|| invalid("unsupported dtype"))
+        })
+        .collect()
+}
+
+struct Dtype {
+    kind: char,
+    size: usize,
+}
+
+impl Dtype {
+    fn parse(descr: &str) -> std::io::Result<Self> {
+        let unsupported = || {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("unsupported dtype '{descr}'"),
+            )
+        };
+
+        let mut chars = descr.chars();
+        let order = chars.next().ok_or_else(unsupported)?;
+        let kind = chars.next().ok_or_else(unsupported)?;
+        let size = chars.as_str().parse().map_err(|_| unsupported())?;
+
+        let dtype = Self { kind, size };
+
+        if !matches!(order, '<' | '|' | '=')
+            || (size > 1 && order == '|')
+            || dtype.decode(&vec![0; size]).is_none()
+        {
+            return Err(unsupported());
+        }
+
+        Ok(dtype)
+    }
+
+    fn decode(&self, value: &[u8]) -> Option<f64> {
+        let value = match (self.kind, self.size) {
+            ('f', 8) => f64::from_le_bytes(value.try_into().ok()?),
+            ('f', 4) => f32::from_le_bytes(value.try_into().ok()?) as f64,
+            ('i', 1) => i8::from_le_bytes(value.try_into().ok()?) as f64,
+            ('i', 2) => i16::from_le_bytes(value.try_into().ok()?) as f64,
+            ('i', 4) => i32::from_le_bytes(value.try_into().ok()?) as f64,
+            ('i', 8) => i64::from_le_bytes(value.try_into().ok()?) as f64,
+            ('u', 1) => u8::from_le_bytes(value.try_into().ok()?) as f64,
+            ('u', 2) => u16::from_le_bytes(value.try_into().ok()?) as f64,
+            ('u', 4) => u32::from_le_bytes(value.try_into().ok()?) as f64,
+            _ => return None,
+        };
+
+        Some(value)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Literal {
+    Bool(bool),
+    Dict(Vec<(String, Literal)>),
+    Int(usize),
+    List(Vec<Literal>),
+    Str(String),
+}
+
+impl Literal {
+    fn parse(s: &str) -> Result<Self, String> {
+        let mut chars = s.trim().chars().peekable();
+        let literal = Self::value(&mut chars)?;
+
+        match chars.find(|c| !c.is_whitespace()) {
+            None => Ok(literal),
+            Some(c) => Err(format!("unexpected '{c}' in header")),
+        }
+    }
+
+    fn value(chars: &mut std::iter::Peekable<std::str::Chars>) -> Result<Self, String> {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        match chars.next() {
+            Some(quote @ ('\'' | '"')) => {
+                let mut string = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('\\') => string.extend(chars.next()),
+                        Some(c) if c == quote => break,
+                        Some(c) => string.push(c),
+                        None => return Err("unterminated string in header".to_string()),
+                    }
+                }
+
+                Ok(Self::Str(string))
+            }
+            Some(open @ ('(' | '[' | '{')) => {
+                let close = match open {
+                    '(' => ')',
+                    '[' => ']',
+                    _ => '}',
+                };
+                let mut items = Vec::new();
+                let mut entries = Vec::new();
+
+                loop {
+                    while chars.next_if(|c| c.is_whitespace() || *c == ',').is_some() {}
+
+                    if chars.next_if_eq(&close).is_some() {
+                        break;
+                    }
+
+                    let value = Self::value(chars)?;
+
+                    if open == '{' {
+                        let Self::Str(key) = value else {
+                            return Err("invalid key in header".to_string());
+                        };
+
+                        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+                        if chars.next() != Some(':') {
+                            return Err("expected ':' in header".to_string());
+                        }
+
+                        entries.push((key, Self::value(chars)?));
+                    } else {
+                        items.push(value);
+                    }
+                }
+
+                if open == '{' {
+                    Ok(Self::Dict(entries))
+                } else {
+                    Ok(Self::List(items))
+                }
+            }
+            Some(c) if c.is_ascii_alphanumeric() => {
+                let mut word = c.to_string();
+
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric()) {
+                    word.push(c);
+                }
+
+                match word.as_str() {
+                    "True" => Ok(Self::Bool(true)),
+                    "False" => Ok(Self::Bool(false)),
+                    _ => word
+                        .trim_end_matches('L')
+                        .parse()
+                        .map(Self::Int)
+                        .map_err(|_| format!("unexpected '{word}' in header")),
+                }
+            }
+            Some(c) => Err(format!("unexpected '{c}' in header")),
+            None => Err("unexpected end of header".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    fn wav(bits: u16, data: &[u8]) -> Vec<u8> {
+        let mut bytes = b"RIFF\0\0\0\0WAVEfmt ".to_vec();
+        bytes.extend(16_u32.to_le_bytes());
+        bytes.extend(1_u16.to_le_bytes());
+        bytes.extend(1_u16.to_le_bytes());
+        bytes.extend(48_000_u32.to_le_bytes());
+        bytes.extend((48_000 * bits as u32 / 8).to_le_bytes());
+        bytes.extend((bits / 8).to_le_bytes());
+        bytes.extend(bits.to_le_bytes());
+        bytes.extend(b"data");
+        bytes.extend((data.len() as u32).to_le_bytes());
+        bytes.extend(data);
+
+        bytes
+    }
+
+    #[test]
+    fn wav_8() {
+        let samples = super::wav(&wav(8, &[0, 128, 192])).unwrap();
+
+        assert_eq!(samples, [-1.0, 0.0, 0.5]);
+    }
+
+    #[test]
+    fn wav_16() {
+        let samples = super::wav(&wav(16, &[0x00, 0x80, 0x00, 0x00, 0x00, 0x40])).unwrap();
+
+        assert_eq!(samples, [-1.0, 0.0, 0.5]);
+    }
+
+    #[test]
+    fn wav_24() {
+        let samples = super::wav(&wav(
+            24,
+            &[0x00, 0x00, 0x80, 0xff, 0xff, 0xff, 0x00, 0x00, 0x40],
+        ))
+        .unwrap();
+
+        assert_eq!(samples, [-1.0, -1.0 / 8_388_608.0, 0.5]);
+    }
+
+    #[test]
+    fn wav_invalid() {
+        assert!(super::wav(b"RIFF\0\0\0\0WAVE").is_err());
+        assert!(super::wav(&wav(12, &[0, 0])).is_err());
+    }
+
+    #[test]
+    fn npy_round_trip() {
+        let path = std::env::temp_dir().join(format!("waveform-{}.npy", std::process::id()));
+        let time = [0.0, 1.0, 2.0];
+        let values = [0.25, -0.5, 1.0];
+
+        crate::export::npy(&path, &[("time (µs)", &time), ("IN 1 (V)", &values)]).unwrap();
+        let samples = super::npy(&std::fs::read(&path).unwrap());
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(samples.unwrap(), values);
+    }
+
+    fn npy(header: &str, data: &[u8]) -> Vec<u8> {
+        let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+        bytes.extend((header.len() as u16).to_le_bytes());
+        bytes.extend(header.as_bytes());
+        bytes.extend(data);
+
+        bytes
+    }
+
+    #[test]
+    fn npy_plain() {
+        let data = [1_i16.to_le_bytes(), (-2_i16).to_le_bytes()].concat();
+        let header = "{'descr': '<i2', 'fortran_order': False, 'shape': (2,), }";
+
+        assert_eq!(super::npy(&npy(header, &data)).unwrap(), [1.0, -2.0]);
+    }
+
+    #[test]
+    fn npy_invalid() {
+        let data = [0; 32];
+        let err =
+            |header: &str, data: &[u8]| super::npy(&npy(header, data)).unwrap_err().to_string();
+
+        assert_eq!(
+            err(
+                "{'descr': '<f8', 'fortran_order': False, 'shape': (2, 2), }",
+                &data
+            ),
+            "only 1-D arrays are supported"
+        );
+        assert_eq!(
+            err(
+                "{'descr': '<f8', 'fortran_order': False, 'shape': (5,), }",
+                &data
+            ),
+            "truncated data"
+        );
+        assert_eq!(
+            err(
+                "{'descr': '>f8', 'fortran_order': False, 'shape': (2,), }",
+                &data
+            ),
+            "unsupported dtype '>f8'"
+        );
+        assert_eq!(
+            err(
+                "{'descr': '<f8', 'fortran_order': True, 'shape': (2,), }",
+                &data
+            ),
+            "Fortran order is not supported"
+        );
+        assert_eq!(
+            err("{'descr': '<f8', 'shape': (2,), }", &data),
+            "missing fortran_order"
+        );
+    }
+
+    #[test]
+    fn csv_header() {
+        let samples = super::csv("time,value\n0,1\n1;2\n2\t3\n\n").unwrap();
+
+        assert_eq!(samples, [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn resample() {
+        let samples = super::resample(&[0.0, 1.0]);
+
+        assert_eq!(samples.len(), super::LEN);
+        assert_eq!(samples[0], 0.0);
+        assert_eq!(samples[super::LEN - 1], 1.0);
+        assert!((samples[super::LEN / 2] - 0.5).abs() < 1e-3);
+        assert_eq!(super::resample(&[0.5]), vec![0.5; super::LEN]);
+    }
+}
@@ -0,0 +1,101 @@
+use gtk::prelude::*;
+
+#[derive(Debug)]
+pub enum InputMsg {
+    Select(u32),
+    Set(f64),
+}
+
+#[derive(Debug)]
+pub enum OutputMsg {
+    Change(f64),
+}
+
+pub struct Options {
+    pub label: &'static str,
+    pub range: (f64, f64),
+    pub current: f64,
+}
+
+pub struct Model {
+    options: Options,
+    steps: Vec<f64>,
+}
+
+#[relm4::component(pub)]
+impl relm4::Component for Model {
+    type CommandOutput = ();
+    type Init = Options;
+    type Input = InputMsg;
+    type Output = OutputMsg;
+
+    fn init(
+        init: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        let steps = crate::scales::steps(init.range);
+        let labels = steps
+            .iter()
+            .map(|step| format!("×{}", (100.0 / step).round() / 100.0))
+            .collect::<Vec<_>>();
+        let labels = labels.iter().map(String::as_str).collect::<Vec<_>>();
+
+        let model = Self {
+            options: init,
+            steps,
+        };
+
+        let widgets = view_output!();
+
+        widgets
+            .dropdown
+            .set_selected(model.position(model.options.current));
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut ModelWidgets,
+        msg: Self::Input,
+        sender: relm4::ComponentSender<Self>,
+        _: &Self::Root,
+    ) {
+        match msg {
+            InputMsg::Select(position) => {
+                if let Some(step) = self.steps.get(position as usize).copied() {
+                    self.options.current = step;
+                    sender.output(OutputMsg::Change(step)).ok();
+                }
+            }
+            InputMsg::Set(value) => {
+                self.options.current = value;
+                widgets.dropdown.set_selected(self.position(value));
+            }
+        };
+    }
+
+    view! {
+        gtk::Frame {
+            set_label: Some(model.options.label),
+            set_tooltip_text: Some("Gain relative to the V/div of the graph"),
+
+            #[name = "dropdown"]
+            gtk::DropDown::from_strings(&labels) {
+                connect_selected_notify[sender] => move |this| {
+                    sender.input(InputMsg::Select(this.selected()));
+                },
+            },
+        }
+    }
+}
+
+impl Model {
+    fn position(&self, value: f64) -> u32 {
+        self.steps
+            .iter()
+            .position(|step| (step - value).abs() <= value.abs() / 1_000.0)
+            .map_or(gtk::INVALID_LIST_POSITION, |position| position as u32)
+    }
+}
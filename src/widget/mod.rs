@@ -1,7 +1,9 @@
+pub mod division;
 pub mod palette;
 pub mod precise;
 pub mod radio;
 
+pub use division::Model as Division;
 pub use palette::Model as Palette;
 pub use precise::Model as PreciseScale;
 pub use radio::Model as RadioGroup;
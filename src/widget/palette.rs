@@ -4,6 +4,7 @@ use gtk::prelude::*;
 pub enum InputMsg {
     Expand,
     Fold,
+    Set(bool),
 }
 
 #[derive(Debug)]
@@ -64,6 +65,7 @@ impl relm4::Component for Model {
                 widgets.parent.set_visible(false);
                 sender.output(OutputMsg::Fold).ok();
             }
+            InputMsg::Set(active) => widgets.toggle.set_active(active),
         }
     }
 
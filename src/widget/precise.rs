@@ -2,8 +2,10 @@ use gtk::prelude::*;
 
 #[derive(Debug)]
 pub enum InputMsg {
+    Display(f64),
     Expand,
     Fold,
+    Set(f64),
 }
 
 #[derive(Debug)]
@@ -54,6 +56,12 @@ impl relm4::Component for Model {
         _: &Self::Root,
     ) {
         match msg {
+            InputMsg::Display(value) => {
+                self.options.value = value;
+                widgets.spin.block_signal(&widgets.changed);
+                widgets.spin.set_value(value);
+                widgets.spin.unblock_signal(&widgets.changed);
+            }
             InputMsg::Expand => {
                 widgets.scale.set_draw_value(false);
                 widgets.spin.show();
@@ -62,6 +70,10 @@ impl relm4::Component for Model {
                 widgets.scale.set_draw_value(true);
                 widgets.spin.hide();
             }
+            InputMsg::Set(value) => {
+                self.options.value = value;
+                widgets.spin.set_value(value);
+            }
         };
     }
 
@@ -111,7 +123,7 @@ impl relm4::Component for Model {
 
                         connect_value_changed[sender] => move |this| {
                             sender.output(OutputMsg::Change(this.value())).ok();
-                        },
+                        } @changed,
                     },
                 },
             },
@@ -1,5 +1,10 @@
 use gtk::prelude::*;
 
+#[derive(Debug)]
+pub enum InputMsg<T: std::fmt::Debug> {
+    Set(T),
+}
+
 #[derive(Debug)]
 pub enum OutputMsg<T: std::fmt::Debug> {
     Change(T),
@@ -21,7 +26,7 @@ impl<T: std::fmt::Debug + std::cmp::PartialEq + std::clone::Clone + std::fmt::Di
     relm4::SimpleComponent for Model<T>
 {
     type Init = Options<T>;
-    type Input = ();
+    type Input = InputMsg<T>;
     type Output = OutputMsg<T>;
 
     fn init(
@@ -71,6 +76,16 @@ impl<T: std::fmt::Debug + std::cmp::PartialEq + std::clone::Clone + std::fmt::Di
         relm4::ComponentParts { model, widgets }
     }
 
+    fn update(&mut self, msg: Self::Input, _: relm4::ComponentSender<Self>) {
+        let InputMsg::Set(value) = msg;
+
+        for (button, option) in &self.radio {
+            if *option == value {
+                button.set_active(true);
+            }
+        }
+    }
+
     view! {
         gtk::Frame {
             #[watch]